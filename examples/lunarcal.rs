@@ -7,21 +7,18 @@
 
 // A simple, option-free version of cal(1) that replaces day numbers with lunar phase emoji for
 // principal phases.
-//
-// It assumes that the current local time offset holds true through the entire month, which may
-// produce inaccuracies.
 
 use chrono::{Datelike, Duration, Local, TimeZone, Weekday};
 use esbat::daily_lunar_phase;
 
 fn main() {
-    let today = Local::today();
+    let today = Local::now().date_naive();
     println!("{:^20}", today.format("%B %Y"));
     println!("Su Mo Tu We Th Fr Sa");
 
-    let first_day = Local.ymd(today.year(), today.month(), 1);
-    let next_month = Local.ymd(today.year(), today.month(), 1) + Duration::days(31);
-    let last_day = Local.ymd(next_month.year(), next_month.month(), 1) - Duration::days(1);
+    let first_day = today.with_day(1).unwrap();
+    let next_month = (first_day + Duration::days(31)).with_day(1).unwrap();
+    let last_day = next_month.pred_opt().unwrap();
 
     print!("{:>width$}", "", width = 3 * first_day.weekday().num_days_from_sunday() as usize);
 
@@ -32,7 +29,9 @@ fn main() {
             print!("\x1b[7m");
         }
 
-        let phase = daily_lunar_phase(day);
+        // Noon always exists in local time, and stands for the whole local day
+        let noon = Local.from_local_datetime(&day.and_hms_opt(12, 0, 0).unwrap()).earliest();
+        let phase = daily_lunar_phase(noon.unwrap());
         if phase.is_principal() {
             print!("{}", phase.as_emoji());
         } else {
@@ -49,7 +48,7 @@ fn main() {
         } else {
             print!(" ");
         }
-        day = day + Duration::days(1);
+        day += Duration::days(1);
     }
 
     for _ in weeks..6 {
@@ -0,0 +1,230 @@
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+
+use crate::calendar::{lunar_apsis_at_or_after, lunar_apsis_at_or_before, lunar_distance};
+use crate::delta_t::{DeltaT, EspenakMeeus};
+use crate::iter::{add_day, moment_bounds};
+use crate::moment::{Moment, Time, Timestamp};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::RangeBounds;
+
+/// The closest and farthest points of the Moon's orbit around the Earth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Apsis {
+    /// The Moon is closest to the Earth.
+    Perigee,
+    /// The Moon is farthest from the Earth.
+    Apogee,
+}
+
+impl Apsis {
+    fn from_perigee(perigee: bool) -> Apsis {
+        if perigee {
+            Apsis::Perigee
+        } else {
+            Apsis::Apogee
+        }
+    }
+}
+
+/// Returns an iterator of lunar perigees and apogees and their moments.
+///
+/// ```
+/// use esbat::{Apsis, FixedDate};
+///
+/// let start = FixedDate::from_gregorian(2020, 4, 1).unwrap().moment();
+/// let end = FixedDate::from_gregorian(2020, 5, 1).unwrap().moment();
+/// let mut iter = esbat::apsis_iter(start..end);
+///
+/// let (apsis, t) = iter.next().unwrap();
+/// assert_eq!(apsis, Apsis::Perigee);
+/// assert_eq!(t.fixed_date(), FixedDate::from_gregorian(2020, 4, 7).unwrap());
+/// assert_eq!(iter.next().unwrap().0, Apsis::Apogee);
+/// assert!(iter.next().is_none());
+/// ```
+pub fn apsis_iter<T, B>(range: B) -> ApsisIter<T>
+where
+    T: Timestamp,
+    B: RangeBounds<T>,
+{
+    let (start, end) = moment_bounds(&range);
+    ApsisIter::new(start, end, EspenakMeeus)
+}
+
+/// Lunar perigee and apogee iterator.
+///
+/// This struct is created by [`apsis_iter`] or
+/// [`Ephemeris::apsis_iter`](crate::Ephemeris::apsis_iter), which determines the ΔT model `M`.
+#[derive(Debug, Clone)]
+pub struct ApsisIter<T = Moment, M = EspenakMeeus> {
+    bound: Option<(f64, f64)>,
+    end_excl: bool,
+    positive: bool,
+    model: M,
+    time: PhantomData<T>,
+}
+
+impl<T: Timestamp, M: DeltaT> ApsisIter<T, M> {
+    pub(crate) fn new(
+        (mut start, start_excl): (f64, bool),
+        (end, end_excl): (f64, bool),
+        model: M,
+    ) -> ApsisIter<T, M> {
+        let positive = start <= end;
+        // An excluded start is usually the moment of an apsis found earlier, which is skipped
+        if start_excl {
+            let (_, first) = if positive {
+                lunar_apsis_at_or_after(start, &model)
+            } else {
+                lunar_apsis_at_or_before(start, &model)
+            };
+            if (first - start).abs() < 1.0 / 1440.0 {
+                start = add_day(first, positive);
+            }
+        }
+        ApsisIter { bound: Some((start, end)), end_excl, positive, model, time: PhantomData }
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> Iterator for ApsisIter<T, M> {
+    type Item = (Apsis, T);
+
+    fn next(&mut self) -> Option<(Apsis, T)> {
+        let (start, end) = self.bound?;
+
+        let (perigee, next) = if self.positive {
+            lunar_apsis_at_or_after(start, &self.model)
+        } else {
+            lunar_apsis_at_or_before(start, &self.model)
+        };
+
+        if let Some(t) = T::from_moment(Moment::from_rd(next)) {
+            let in_range = match (self.positive, self.end_excl) {
+                (true, false) => next <= end,
+                (true, true) => next < end,
+                (false, false) => next >= end,
+                (false, true) => next > end,
+            };
+            if in_range {
+                self.bound = Some((add_day(next, self.positive), end));
+                return Some((Apsis::from_perigee(perigee), t));
+            }
+        }
+
+        self.bound = None;
+        None
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> FusedIterator for ApsisIter<T, M> {}
+
+/// A full or new moon that appears especially large or small.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MoonSize {
+    /// The Moon is near perigee.
+    Supermoon,
+    /// The Moon is near apogee.
+    Micromoon,
+}
+
+/// The definition used to decide whether a full or new moon is a supermoon or micromoon.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SupermoonRule {
+    /// Richard Nolle's definition: the Moon is within 90% of its closest approach (for a
+    /// supermoon) or farthest retreat (for a micromoon) in its current orbit, as measured between
+    /// the perigee and apogee on either side of the full or new moon.
+    Nolle,
+    /// The Moon is closer than `supermoon` kilometers (for a supermoon) or farther than
+    /// `micromoon` kilometers (for a micromoon) from the Earth.
+    Distance {
+        /// The greatest distance of a supermoon, in kilometers.
+        supermoon: f64,
+        /// The least distance of a micromoon, in kilometers.
+        micromoon: f64,
+    },
+}
+
+/// Determines whether a full or new moon is a supermoon or micromoon.
+///
+/// `t` should be the moment of a full moon or new moon, such as those returned by
+/// [`lunar_phase_iter`](crate::lunar_phase_iter). Returns `None` if the Moon is neither.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{TimeZone, Utc};
+/// use esbat::{moon_size, MoonSize, SupermoonRule};
+///
+/// let t = Utc.with_ymd_and_hms(2020, 4, 8, 2, 35, 0).unwrap();
+/// assert_eq!(moon_size(t, SupermoonRule::Nolle), Some(MoonSize::Supermoon));
+///
+/// let t = Utc.with_ymd_and_hms(2020, 10, 31, 14, 49, 0).unwrap();
+/// assert_eq!(moon_size(t, SupermoonRule::Nolle), Some(MoonSize::Micromoon));
+///
+/// let rule = SupermoonRule::Distance { supermoon: 360_000.0, micromoon: 405_000.0 };
+/// assert_eq!(moon_size(t, rule), Some(MoonSize::Micromoon));
+/// # }
+/// ```
+pub fn moon_size<T: Time>(t: T, rule: SupermoonRule) -> Option<MoonSize> {
+    moon_size_with(t, rule, &EspenakMeeus)
+}
+
+pub(crate) fn moon_size_with<T: Time>(
+    t: T,
+    rule: SupermoonRule,
+    dt: &dyn DeltaT,
+) -> Option<MoonSize> {
+    let t = t.to_moment().rd();
+    let distance = lunar_distance(t, dt) / 1000.0;
+    let (supermoon, micromoon) = match rule {
+        SupermoonRule::Nolle => {
+            // The perigee and apogee on either side of `t` bound the current orbit
+            let (perigee_first, before) = lunar_apsis_at_or_before(t, dt);
+            let (_, after) = lunar_apsis_at_or_after(t, dt);
+            let (perigee, apogee) = if perigee_first { (before, after) } else { (after, before) };
+            let perigee = lunar_distance(perigee, dt) / 1000.0;
+            let apogee = lunar_distance(apogee, dt) / 1000.0;
+            (apogee - 0.9 * (apogee - perigee), perigee + 0.9 * (apogee - perigee))
+        }
+        SupermoonRule::Distance { supermoon, micromoon } => (supermoon, micromoon),
+    };
+    if distance <= supermoon {
+        Some(MoonSize::Supermoon)
+    } else if distance >= micromoon {
+        Some(MoonSize::Micromoon)
+    } else {
+        None
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_apsides() {
+    use crate::util::assert_events;
+    use chrono::{TimeZone, Utc};
+    use core::ops::Bound::{Excluded, Unbounded};
+
+    // Perigee and apogee times from Fred Espenak, "Moon at Perigee and Apogee: 2001 to 2100"
+    let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2020, 3, 1, 0, 0, 0).unwrap();
+    let expected = [
+        (Apsis::Apogee, Utc.with_ymd_and_hms(2020, 1, 2, 1, 29, 0).unwrap()),
+        (Apsis::Perigee, Utc.with_ymd_and_hms(2020, 1, 13, 20, 21, 0).unwrap()),
+        (Apsis::Apogee, Utc.with_ymd_and_hms(2020, 1, 29, 21, 28, 0).unwrap()),
+        (Apsis::Perigee, Utc.with_ymd_and_hms(2020, 2, 10, 20, 28, 0).unwrap()),
+        (Apsis::Apogee, Utc.with_ymd_and_hms(2020, 2, 26, 11, 35, 0).unwrap()),
+    ];
+    assert_events(apsis_iter(start..end), &expected, 15);
+
+    let mut iter = apsis_iter(end..start);
+    assert_eq!(iter.next().unwrap().0, Apsis::Apogee);
+    assert_eq!(iter.count(), 4);
+
+    for (apsis, t) in apsis_iter(start..end) {
+        assert_ne!(apsis_iter((Excluded(t), Unbounded)).next().unwrap().0, apsis);
+    }
+}
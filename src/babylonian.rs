@@ -0,0 +1,164 @@
+// Copyright (c) 2018 Edward M. Reingold and Nachum Dershowitz
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+//
+// Functions from chapter 20, "Astronomical Lunar Calendars", of:
+//
+//     E. M. Reingold and N. Dershowitz, Calendrical Calculations: The Ultimate Edition.
+//         Cambridge University Press, 2018. doi:10.1017/9781107415058
+//
+// See `calendar.rs` for the representation of dates and moments. Months are numbered from Nisannu
+// (1) to Addaru (12); the leap month is Addaru II (12), or Ululu II (6) in the 18th year of the
+// cycle.
+
+#![allow(clippy::unreadable_literal)]
+
+use crate::calendar::{babylonian_visible_crescent, phasis_on_or_before, MEAN_SYNODIC_MONTH};
+use crate::delta_t::{DeltaT, EspenakMeeus};
+use crate::location::Location;
+use crate::moment::{CivilDate, Day, FixedDate};
+use crate::util::{i32, nonneg, round};
+use core::convert::TryFrom;
+
+// fixed_from_julian(-311, 4, 3), the beginning of the Seleucid era
+const BABYLONIAN_EPOCH: i32 = -113502;
+
+fn babylon() -> Location {
+    Location::new(32.475, 44.416667, 26.0)
+}
+
+fn is_babylonian_leap_year(year: i32) -> bool {
+    (7 * year + 13).rem_euclid(19) < 7
+}
+
+// Returns `None` if the crescent is not predicted to be seen in the month before `date`, which
+// only happens with an unrealistic ΔT model
+fn babylonian_new_month_on_or_before(date: i32, dt: &dyn DeltaT) -> Option<i32> {
+    phasis_on_or_before(date, |d| babylonian_visible_crescent(d, babylon(), dt), dt)
+}
+
+fn fixed_from_babylonian(
+    year: i32,
+    month: u32,
+    leap: bool,
+    day: u32,
+    dt: &dyn DeltaT,
+) -> Option<i32> {
+    let index = if leap || (year.rem_euclid(19) == 18 && month > 6) {
+        month
+    } else {
+        month.checked_sub(1)?
+    };
+    let months = ((year - 1) * 235 + 13).div_euclid(19) + i32::try_from(index).ok()?;
+    let midmonth = BABYLONIAN_EPOCH + i32(round(MEAN_SYNODIC_MONTH * f64::from(months))) + 15;
+    Some(babylonian_new_month_on_or_before(midmonth, dt)? + i32::try_from(day).ok()? - 1)
+}
+
+fn babylonian_from_fixed(date: i32, dt: &dyn DeltaT) -> Option<(i32, u32, bool, u32)> {
+    let crescent = babylonian_new_month_on_or_before(date, dt)?;
+    let months = i32(round(f64::from(crescent - BABYLONIAN_EPOCH) / MEAN_SYNODIC_MONTH));
+    let year = (19 * months + 5).div_euclid(235) + 1;
+    let approx = BABYLONIAN_EPOCH
+        + i32(round(f64::from(((year - 1) * 235 + 13).div_euclid(19)) * MEAN_SYNODIC_MONTH));
+    let new_year = babylonian_new_month_on_or_before(approx + 15, dt)?;
+    let index = nonneg(i32(round(f64::from(crescent - new_year) / 29.5)));
+    let special = year.rem_euclid(19) == 18;
+    let leap = if special { index == 6 } else { index == 12 };
+    let month = if leap || (special && index > 6) { index } else { index + 1 };
+    Some((year, month, leap, nonneg(date - crescent + 1)))
+}
+
+// =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=
+
+/// A date in the Babylonian calendar, as reconstructed by <i>Calendrical Calculations</i>.
+///
+/// Each month began on the evening the crescent moon was first seen at Babylon, which is predicted
+/// here with a criterion of moon age and lag between sunset and moonset. Years are counted in the
+/// Seleucid era, beginning in the spring of 311 BCE. Seven of every nineteen years have a leap
+/// month.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BabylonianDate {
+    /// The year of the Seleucid era.
+    pub year: i32,
+    /// The month, from 1 (Nisannu) to 12 (Addaru).
+    pub month: u32,
+    /// True if the month is the leap month, which follows the regular month of the same number.
+    pub leap: bool,
+    /// The day of the month, from 1 to 30.
+    pub day: u32,
+}
+
+impl BabylonianDate {
+    /// Converts a date in the proleptic Gregorian calendar to the Babylonian calendar.
+    ///
+    /// The Babylonian day begins at sunset; this returns the Babylonian date that begins at sunset
+    /// on the evening before `date`. Returns `None` if no crescent is predicted to be seen in the
+    /// month before `date`, which can only happen with an unrealistic ΔT model passed to
+    /// [`Ephemeris::babylonian_from_gregorian`](crate::Ephemeris::babylonian_from_gregorian).
+    ///
+    /// ```
+    /// use esbat::{BabylonianDate, FixedDate};
+    ///
+    /// let date = BabylonianDate::from_gregorian(FixedDate::from_gregorian(2020, 4, 25).unwrap());
+    /// assert_eq!(date, Some(BabylonianDate { year: 2331, month: 1, leap: false, day: 1 }));
+    /// ```
+    pub fn from_gregorian<D: Day>(date: D) -> Option<BabylonianDate> {
+        BabylonianDate::from_gregorian_with(date, &EspenakMeeus)
+    }
+
+    pub(crate) fn from_gregorian_with<D: Day>(date: D, dt: &dyn DeltaT) -> Option<BabylonianDate> {
+        let (year, month, leap, day) = babylonian_from_fixed(date.fixed_date().rd(), dt)?;
+        Some(BabylonianDate { year, month, leap, day })
+    }
+
+    /// Converts the date in the Babylonian calendar to the proleptic Gregorian calendar.
+    ///
+    /// Returns `None` if no crescent is predicted to be seen near the start of the month, or if
+    /// the date cannot be represented by `D`.
+    pub fn to_gregorian<D: CivilDate>(self) -> Option<D> {
+        self.to_gregorian_with(&EspenakMeeus)
+    }
+
+    pub(crate) fn to_gregorian_with<D: CivilDate>(self, dt: &dyn DeltaT) -> Option<D> {
+        let date = fixed_from_babylonian(self.year, self.month, self.leap, self.day, dt)?;
+        D::from_fixed(FixedDate::from_rd(date))
+    }
+
+    /// Returns true if `year` is a leap year, which has thirteen months.
+    ///
+    /// ```
+    /// # use esbat::BabylonianDate;
+    /// assert!(BabylonianDate::is_leap_year(1));
+    /// assert!(!BabylonianDate::is_leap_year(2));
+    /// ```
+    pub fn is_leap_year(year: i32) -> bool {
+        is_babylonian_leap_year(year)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_babylonian() {
+    let dt = &EspenakMeeus;
+    assert_eq!(BABYLONIAN_EPOCH, crate::calendar::fixed_from_julian(-311, 4, 3));
+
+    // The Seleucid era begins on 1 Nisannu
+    assert_eq!(babylonian_from_fixed(BABYLONIAN_EPOCH, dt), Some((1, 1, false, 1)));
+
+    // Dates throughout the first cycle convert back to themselves, with the leap months in the
+    // expected years
+    let mut leap_months = Vec::new();
+    for date in (BABYLONIAN_EPOCH..BABYLONIAN_EPOCH + 19 * 366).step_by(13) {
+        let (year, month, leap, day) = babylonian_from_fixed(date, dt).unwrap();
+        assert_eq!(fixed_from_babylonian(year, month, leap, day, dt), Some(date));
+        if leap && leap_months.last() != Some(&(year, month)) {
+            assert!(is_babylonian_leap_year(year));
+            leap_months.push((year, month));
+        }
+    }
+    assert_eq!(leap_months, [(1, 12), (4, 12), (7, 12), (9, 12), (12, 12), (15, 12), (18, 6)]);
+}
@@ -0,0 +1,166 @@
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+
+use crate::calendar::{
+    lunar_phase_at_or_after, lunar_phase_at_or_before, solar_longitude, solar_longitude_after,
+    solar_longitude_before, MEAN_SYNODIC_MONTH,
+};
+use crate::delta_t::{DeltaT, EspenakMeeus};
+use crate::moment::{Moment, Time};
+use crate::util::{clamp_angle, floor};
+
+pub(crate) const NEW_MOON: f64 = 0.0;
+pub(crate) const FULL_MOON: f64 = 180.0;
+
+// Returns true if a lunar phase `phase` occurred earlier in the calendar month of `t`, in the time
+// zone of `t`.
+pub(crate) fn second_in_month<T: Time>(phase: f64, t: T, dt: &dyn DeltaT) -> bool {
+    let prev = lunar_phase_at_or_before(phase, t.to_moment().rd() - 1.0, dt);
+    match t.with_moment(Moment::from_rd(prev)) {
+        Some(prev) => {
+            let (year, month, _) = prev.fixed_date().to_gregorian();
+            let (t_year, t_month, _) = t.fixed_date().to_gregorian();
+            year == t_year && month == t_month
+        }
+        None => false,
+    }
+}
+
+// Returns true if `t` is the third of four lunar phases `phase` that occur in the astronomical
+// season containing `t`.
+pub(crate) fn third_of_four_in_season<T: Time>(phase: f64, t: T, dt: &dyn DeltaT) -> bool {
+    let t = t.to_moment().rd();
+    let lambda = floor(solar_longitude(t, dt) / 90.0) * 90.0;
+    let start = solar_longitude_before(lambda, t, dt);
+    let end = solar_longitude_after(clamp_angle(lambda + 90.0), t, dt);
+
+    let mut count = 0;
+    let mut index = None;
+    let mut next = lunar_phase_at_or_after(phase, start, dt);
+    while next < end {
+        count += 1;
+        if (next - t).abs() < 1.0 {
+            index = Some(count);
+        }
+        next = lunar_phase_at_or_after(phase, next + MEAN_SYNODIC_MONTH / 2.0, dt);
+    }
+    count == 4 && index == Some(3)
+}
+
+/// Returns true if the full moon at moment `t` is a blue moon: the second full moon in a calendar
+/// month.
+///
+/// `t` should be the moment of a full moon, such as one returned by
+/// [`lunar_phase_iter`](crate::lunar_phase_iter). The calendar month is determined in the time
+/// zone of `t`; convert `t` to a different time zone to use that zone's calendar months.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{FixedOffset, TimeZone, Utc};
+///
+/// let t = Utc.with_ymd_and_hms(2020, 10, 31, 14, 49, 0).unwrap();
+/// assert!(esbat::is_blue_moon(t));
+/// // The same full moon falls on November 1 at UTC+10
+/// assert!(!esbat::is_blue_moon(t.with_timezone(&FixedOffset::east_opt(10 * 3600).unwrap())));
+/// # }
+/// ```
+pub fn is_blue_moon<T: Time>(t: T) -> bool {
+    second_in_month(FULL_MOON, t, &EspenakMeeus)
+}
+
+/// Returns true if the full moon at moment `t` is a seasonal blue moon: the third of four full
+/// moons in an astronomical season.
+///
+/// `t` should be the moment of a full moon, such as one returned by
+/// [`lunar_phase_iter`](crate::lunar_phase_iter). The seasons begin at the equinoxes and
+/// solstices.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{TimeZone, Utc};
+///
+/// let t = Utc.with_ymd_and_hms(2021, 8, 22, 12, 2, 0).unwrap();
+/// assert!(esbat::is_seasonal_blue_moon(t));
+/// # }
+/// ```
+pub fn is_seasonal_blue_moon<T: Time>(t: T) -> bool {
+    third_of_four_in_season(FULL_MOON, t, &EspenakMeeus)
+}
+
+/// Returns true if the new moon at moment `t` is a black moon: the second new moon in a calendar
+/// month.
+///
+/// `t` should be the moment of a new moon, such as one returned by
+/// [`lunar_phase_iter`](crate::lunar_phase_iter). The calendar month is determined in the time
+/// zone of `t`; convert `t` to a different time zone to use that zone's calendar months.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{TimeZone, Utc};
+///
+/// let t = Utc.with_ymd_and_hms(2019, 8, 30, 10, 37, 0).unwrap();
+/// assert!(esbat::is_black_moon(t));
+/// # }
+/// ```
+pub fn is_black_moon<T: Time>(t: T) -> bool {
+    second_in_month(NEW_MOON, t, &EspenakMeeus)
+}
+
+/// Returns true if the new moon at moment `t` is a seasonal black moon: the third of four new
+/// moons in an astronomical season.
+///
+/// `t` should be the moment of a new moon, such as one returned by
+/// [`lunar_phase_iter`](crate::lunar_phase_iter). The seasons begin at the equinoxes and
+/// solstices.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{TimeZone, Utc};
+///
+/// let t = Utc.with_ymd_and_hms(2020, 8, 19, 2, 42, 0).unwrap();
+/// assert!(esbat::is_seasonal_black_moon(t));
+/// # }
+/// ```
+pub fn is_seasonal_black_moon<T: Time>(t: T) -> bool {
+    third_of_four_in_season(NEW_MOON, t, &EspenakMeeus)
+}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_blue_moons() {
+    use crate::phase::PrincipalPhase;
+    use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    let start = Utc.with_ymd_and_hms(2018, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let moons = crate::lunar_phase_iter(start..end).collect::<Vec<_>>();
+    let filter = |phase, f: fn(DateTime<Utc>) -> bool| {
+        moons
+            .iter()
+            .filter(|(p, t)| *p == phase && f(*t))
+            .map(|(_, t)| t.date_naive())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        filter(PrincipalPhase::FullMoon, is_blue_moon),
+        [date(2018, 1, 31), date(2018, 3, 31), date(2020, 10, 31), date(2023, 8, 31)]
+    );
+    assert_eq!(
+        filter(PrincipalPhase::FullMoon, is_seasonal_blue_moon),
+        [date(2019, 5, 18), date(2021, 8, 22)]
+    );
+    assert_eq!(
+        filter(PrincipalPhase::NewMoon, is_black_moon),
+        [date(2019, 8, 30), date(2022, 4, 30)]
+    );
+    assert_eq!(
+        filter(PrincipalPhase::NewMoon, is_seasonal_black_moon),
+        [date(2020, 8, 19), date(2023, 5, 19)]
+    );
+}
@@ -15,9 +15,9 @@
 // proleptic Gregorian calendar. R.D. is represented as `i32` (which can represent all days
 // representable by Chrono).
 //
-// Moments (e.g. Chrono's `DateTime`) are Rata Die. For example, 1.0 R.D. is Monday, January 1, 1
-// at midnight UTC, and 730120.5 R.D. is January 1, 2000, at 12:00 UTC. Moments are represented as
-// `f64`.
+// Moments (e.g. the public `Moment` type, and Chrono's `DateTime`) are Rata Die. For example, 1.0
+// R.D. is Monday, January 1, 1 at midnight UTC, and 730120.5 R.D. is January 1, 2000, at 12:00
+// UTC. Moments are represented as `f64`.
 //
 // The (year, month, date) structure is represented as (i32, u32, u32) for simple interoperability
 // with Chrono.
@@ -27,7 +27,11 @@
 #![allow(clippy::unreadable_literal, clippy::wildcard_imports)]
 
 use crate::data::*;
+use crate::delta_t::DeltaT;
+use crate::location::Location;
+use crate::moment::Moment;
 use crate::util::*;
+use crate::util::{floor, powi, rem_euclid, round, sqrt};
 
 fn is_gregorian_leap_year(year: i32) -> bool {
     year.rem_euclid(4) == 0 && ![100, 200, 300].contains(&year.rem_euclid(400))
@@ -57,8 +61,8 @@ fn gregorian_new_year(year: i32) -> i32 {
     fixed_from_gregorian(year, 1, 1)
 }
 
-fn gregorian_year_from_fixed(date: f64) -> i32 {
-    let d0 = i32((date - 1.0).floor());
+pub(crate) fn gregorian_year_from_fixed(date: f64) -> i32 {
+    let d0 = i32(floor(date - 1.0));
     let n400 = d0.div_euclid(146097);
     let d1 = d0.rem_euclid(146097);
     let n100 = d1.div_euclid(36524);
@@ -76,7 +80,7 @@ fn gregorian_year_from_fixed(date: f64) -> i32 {
 
 pub(crate) fn gregorian_from_fixed(date: f64) -> (i32, u32, u32) {
     let year = gregorian_year_from_fixed(date);
-    let date = i32(date.floor());
+    let date = i32(floor(date));
     let prior_days = date - gregorian_new_year(year);
     let correction = if date < fixed_from_gregorian(year, 3, 1) {
         0
@@ -90,82 +94,100 @@ pub(crate) fn gregorian_from_fixed(date: f64) -> (i32, u32, u32) {
     (year, month, day)
 }
 
-// =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=
+fn is_julian_leap_year(year: i32) -> bool {
+    year.rem_euclid(4) == if year > 0 { 0 } else { 3 }
+}
 
-fn ephemeris_correction(t: f64) -> f64 {
-    let year_i = gregorian_year_from_fixed(t);
-    let year = f64::from(year_i);
-    match year_i {
-        2051..=2150 => {
-            (-20.0 + 32.0 * ((year - 1820.0) / 100.0).powi(2) + 0.5628 * (2150.0 - year)) / 86400.0
-        }
-        2006..=2050 => polynomial(year - 2000.0, &[62.92, 0.32217, 0.005589]) / 86400.0,
-        1987..=2005 => {
-            polynomial(
-                year - 2000.0,
-                &[63.86, 0.3345, -0.060374, 0.0017275, 0.000651814, 0.00002373599],
-            ) / 86400.0
-        }
-        1900..=1986 => polynomial(
-            f64::from(fixed_from_gregorian(year_i, 7, 1) - fixed_from_gregorian(1900, 1, 1))
-                / 36525.0,
-            &[-0.00002, 0.000297, 0.025184, -0.181133, 0.553040, -0.861938, 0.677066, -0.212591],
-        ),
-        1800..=1899 => polynomial(
-            f64::from(fixed_from_gregorian(year_i, 7, 1) - fixed_from_gregorian(1900, 1, 1))
-                / 36525.0,
-            &[
-                -0.000009, 0.003844, 0.083563, 0.865736, 4.867575, 15.845535, 31.332267, 38.291999,
-                28.316289, 11.636204, 2.043794,
-            ],
-        ),
-        1700..=1799 => {
-            polynomial(year - 1700.0, &[8.118780842, -0.005092142, 0.003336121, -0.0000266484])
-                / 86400.0
-        }
-        1600..=1699 => {
-            polynomial(year - 1600.0, &[120.0, -0.9808, -0.01532, 0.000140272128]) / 86400.0
-        }
-        500..=1599 => {
-            polynomial(
-                (year - 1000.0) / 100.0,
-                &[1574.2, -556.01, 71.23472, 0.319781, -0.8503463, -0.005050998, 0.0083572073],
-            ) / 86400.0
-        }
-        -499..=499 => {
-            polynomial(
-                year / 100.0,
-                &[10583.6, -1014.41, 33.78311, -5.952053, -0.1798452, 0.022174192, 0.0090316521],
-            ) / 86400.0
+// Julian years are numbered without a year 0, so that 1 BCE is -1
+#[allow(clippy::cast_possible_wrap)]
+pub(crate) fn fixed_from_julian(year: i32, month: u32, day: u32) -> i32 {
+    debug_assert!(year != 0);
+    debug_assert!(month <= 2147483647 && day <= 2147483647);
+    let month = month as i32;
+    let day = day as i32;
+
+    let y = if year < 0 { year + 1 } else { year };
+    -2 + 365 * (y - 1)
+        + (y - 1).div_euclid(4)
+        + (367 * month - 362) / 12
+        + if month <= 2 {
+            0
+        } else if is_julian_leap_year(year) {
+            -1
+        } else {
+            -2
         }
-        _ => polynomial((year - 1820.0) / 100.0, &[-20.0, 0.0, 32.0]) / 86400.0,
-    }
+        + day
 }
 
-fn dynamical_from_universal(t: f64) -> f64 {
-    t + ephemeris_correction(t)
+// The first date after `date` that is the `k`th day of the week, counting from Sunday (0)
+pub(crate) fn kday_after(k: i32, date: i32) -> i32 {
+    date + 7 - (date - k).rem_euclid(7)
 }
 
-fn universal_from_dynamical(t: f64) -> f64 {
-    t - ephemeris_correction(t)
+// =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=
+
+// ΔT in days, given that `dt` returns it in seconds
+fn ephemeris_correction(t: f64, dt: &dyn DeltaT) -> f64 {
+    dt.delta_t(Moment::from_rd(t)) / 86400.0
+}
+
+fn dynamical_from_universal(t: f64, dt: &dyn DeltaT) -> f64 {
+    t + ephemeris_correction(t, dt)
+}
+
+pub(crate) fn universal_from_dynamical(t: f64, dt: &dyn DeltaT) -> f64 {
+    t - ephemeris_correction(t, dt)
 }
 
-const J2000: f64 = 730120.5;
+pub(crate) const J2000: f64 = 730120.5;
 
-fn julian_centuries(t: f64) -> f64 {
-    (dynamical_from_universal(t) - J2000) / 36525.0
+fn julian_centuries(t: f64, dt: &dyn DeltaT) -> f64 {
+    (dynamical_from_universal(t, dt) - J2000) / 36525.0
 }
 
 // =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=
 
-fn solar_longitude(t: f64) -> f64 {
-    let c = julian_centuries(t);
+pub(crate) fn solar_longitude(t: f64, dt: &dyn DeltaT) -> f64 {
+    let c = julian_centuries(t, dt);
     let lambda = 282.7771834
         + 36000.76953744 * c
         + 0.000005729577951308232 * sigma(&SOLAR_LONGITUDE_TABLE, |(x, y, z)| x * sin(y + z * c));
     clamp_angle(lambda + aberration(c) + nutation(c))
 }
 
+pub(crate) const MEAN_TROPICAL_YEAR: f64 = 365.242189;
+
+pub(crate) fn solar_longitude_after(lambda: f64, t: f64, dt: &dyn DeltaT) -> f64 {
+    let rate = MEAN_TROPICAL_YEAR / 360.0;
+    let tau = t + rate * clamp_angle(lambda - solar_longitude(t, dt));
+    inv_angle(|x| solar_longitude(x, dt), lambda, t.max(tau - 5.0), tau + 5.0)
+}
+
+pub(crate) fn solar_longitude_before(lambda: f64, t: f64, dt: &dyn DeltaT) -> f64 {
+    let rate = MEAN_TROPICAL_YEAR / 360.0;
+    let tau = t - rate * clamp_angle(solar_longitude(t, dt) - lambda);
+    inv_angle(|x| solar_longitude(x, dt), lambda, tau - 5.0, t.min(tau + 5.0))
+}
+
+pub(crate) fn estimate_prior_solar_longitude(lambda: f64, t: f64, dt: &dyn DeltaT) -> f64 {
+    let rate = MEAN_TROPICAL_YEAR / 360.0;
+    let tau = t - rate * clamp_angle(solar_longitude(t, dt) - lambda);
+    let cap_delta = clamp_angle(solar_longitude(tau, dt) - lambda + 180.0) - 180.0;
+    t.min(tau - rate * cap_delta)
+}
+
+// Distance from the center of the Earth to the center of the Sun, in meters
+pub(crate) fn solar_distance(t: f64, dt: &dyn DeltaT) -> f64 {
+    let c = julian_centuries(t, dt);
+    let anomaly = solar_anomaly(c);
+    let e = polynomial(c, &[0.016708634, -0.000042037, -0.0000001267]);
+    let center = polynomial(c, &[1.914602, -0.004817, -0.000014]) * sin(anomaly)
+        + polynomial(c, &[0.019993, -0.000101]) * sin(2.0 * anomaly)
+        + 0.000289 * sin(3.0 * anomaly);
+    149597870700.0 * 1.000001018 * (1.0 - e * e) / (1.0 + e * cos(anomaly + center))
+}
+
 // Takes `julian_centuries(t)` instead of `t`
 fn nutation(c: f64) -> f64 {
     let a = polynomial(c, &[124.90, -1934.134, 0.002063]);
@@ -178,9 +200,107 @@ fn aberration(c: f64) -> f64 {
     0.0000974 * cos(177.63 + 35999.01848 * c) - 0.005575
 }
 
-const MEAN_SYNODIC_MONTH: f64 = 29.530588861;
+fn obliquity(t: f64, dt: &dyn DeltaT) -> f64 {
+    let c = julian_centuries(t, dt);
+    23.0 + 26.0 / 60.0 + 21.448 / 3600.0
+        + polynomial(c, &[0.0, -46.8150 / 3600.0, -0.00059 / 3600.0, 0.001813 / 3600.0])
+}
+
+// Precession of the equinoxes in ecliptic longitude since J2000
+pub(crate) fn precession(t: f64, dt: &dyn DeltaT) -> f64 {
+    let c = julian_centuries(t, dt);
+    let eta =
+        clamp_angle(polynomial(c, &[0.0, 47.0029 / 3600.0, -0.03302 / 3600.0, 0.00006 / 3600.0]));
+    let cap_p = clamp_angle(polynomial(c, &[174.876384, -869.8089 / 3600.0, 0.03536 / 3600.0]));
+    let p =
+        clamp_angle(polynomial(c, &[0.0, 5029.0966 / 3600.0, 1.11113 / 3600.0, 0.000006 / 3600.0]));
+    let cap_a = cos(eta) * sin(cap_p);
+    let cap_b = cos(cap_p);
+    clamp_angle(p + cap_p - arctan(cap_a, cap_b))
+}
 
-fn nth_new_moon(n: i32) -> f64 {
+fn declination(t: f64, beta: f64, lambda: f64, dt: &dyn DeltaT) -> f64 {
+    let varepsilon = obliquity(t, dt);
+    arcsin(sin(beta) * cos(varepsilon) + cos(beta) * sin(varepsilon) * sin(lambda))
+}
+
+fn right_ascension(t: f64, beta: f64, lambda: f64, dt: &dyn DeltaT) -> f64 {
+    let varepsilon = obliquity(t, dt);
+    arctan(sin(lambda) * cos(varepsilon) - tan(beta) * sin(varepsilon), cos(lambda))
+}
+
+fn sidereal_from_moment(t: f64) -> f64 {
+    let c = (t - J2000) / 36525.0;
+    clamp_angle(polynomial(
+        c,
+        &[280.46061837, 36525.0 * 360.98564736629, 0.000387933, -(38710000_f64.recip())],
+    ))
+}
+
+// Altitude of a body at geocentric ecliptic coordinates (`beta`, `lambda`), as seen from
+// `location`
+fn altitude(t: f64, beta: f64, lambda: f64, location: Location, dt: &dyn DeltaT) -> f64 {
+    let phi = location.latitude();
+    let psi = location.longitude();
+    let alpha = right_ascension(t, beta, lambda, dt);
+    let delta = declination(t, beta, lambda, dt);
+    let theta0 = sidereal_from_moment(t);
+    let cap_h = clamp_angle(theta0 + psi - alpha);
+    let altitude = arcsin(sin(phi) * sin(delta) + cos(phi) * cos(delta) * cos(cap_h));
+    rem_euclid(altitude + 180.0, 360.0) - 180.0
+}
+
+// Atmospheric refraction at the horizon, plus the dip of the horizon for an elevated observer
+fn refraction(location: Location) -> f64 {
+    let h = location.elevation().max(0.0);
+    let cap_r = 6.372e6;
+    let dip = arccos(cap_r / (cap_r + h));
+    34.0 / 60.0 + dip + 19.0 / 3600.0 * sqrt(h)
+}
+
+// The altitudes of the Sun and Moon change by at most about 15 degrees per hour, so hourly samples
+// will not miss a rise or set except where the body barely grazes the horizon.
+const RISE_SET_STEP: f64 = 1.0 / 24.0;
+
+fn solar_altitude(t: f64, location: Location, dt: &dyn DeltaT) -> f64 {
+    altitude(t, 0.0, solar_longitude(t, dt), location, dt)
+}
+
+// First moment in [start, end) when the Sun rises to `alpha` degrees below the horizon
+pub(crate) fn dawn(
+    start: f64,
+    end: f64,
+    location: Location,
+    alpha: f64,
+    dt: &dyn DeltaT,
+) -> Option<f64> {
+    find_crossing(|t| solar_altitude(t, location, dt) + alpha, start, end, RISE_SET_STEP, true)
+}
+
+// First moment in [start, end) when the Sun sets to `alpha` degrees below the horizon
+pub(crate) fn dusk(
+    start: f64,
+    end: f64,
+    location: Location,
+    alpha: f64,
+    dt: &dyn DeltaT,
+) -> Option<f64> {
+    find_crossing(|t| solar_altitude(t, location, dt) + alpha, start, end, RISE_SET_STEP, false)
+}
+
+// First sunrise (upper limb) in [start, end)
+pub(crate) fn sunrise(start: f64, end: f64, location: Location, dt: &dyn DeltaT) -> Option<f64> {
+    dawn(start, end, location, refraction(location) + 16.0 / 60.0, dt)
+}
+
+// First sunset (upper limb) in [start, end)
+pub(crate) fn sunset(start: f64, end: f64, location: Location, dt: &dyn DeltaT) -> Option<f64> {
+    dusk(start, end, location, refraction(location) + 16.0 / 60.0, dt)
+}
+
+pub(crate) const MEAN_SYNODIC_MONTH: f64 = 29.530588861;
+
+pub(crate) fn nth_new_moon(n: i32, dt: &dyn DeltaT) -> f64 {
     let k = f64::from(n - 24724);
     let c = k / 1236.85;
     let approx = J2000
@@ -197,61 +317,146 @@ fn nth_new_moon(n: i32) -> f64 {
     let omega = polynomial(c, &[124.7746, -1.56375588 * 1236.85, 0.0020672, 0.00000215]);
     let correction = -0.00017 * sin(omega)
         + sigma(&NTH_NEW_MOON_CORRECTION_TABLE, |(v, w, x, y, z)| {
-            v * e.powi(w) * sin(x * solar_anomaly + y * lunar_anomaly + z * moon_argument)
+            v * powi(e, w) * sin(x * solar_anomaly + y * lunar_anomaly + z * moon_argument)
         });
     let extra = 0.000325 * sin(polynomial(c, &[299.77, 132.8475848, -0.009173]));
     let additional = sigma(&NTH_NEW_MOON_ADDITIONAL_TABLE, |(i, j, l)| l * sin(i + j * k));
-    universal_from_dynamical(approx + correction + extra + additional)
+    universal_from_dynamical(approx + correction + extra + additional, dt)
 }
 
-#[cfg(test)]
 #[allow(clippy::maybe_infinite_iter)]
-fn new_moon_at_or_after(t: f64) -> f64 {
-    let t0 = nth_new_moon(0);
-    let phi = lunar_phase(t);
-    let n = i32((((t - t0) / MEAN_SYNODIC_MONTH) - (phi / 360.0)).round());
-    nth_new_moon((n..).find(|k| nth_new_moon(*k) >= t).unwrap())
+pub(crate) fn new_moon_at_or_after(t: f64, dt: &dyn DeltaT) -> f64 {
+    let t0 = nth_new_moon(0, dt);
+    let phi = lunar_phase(t, dt);
+    let n = i32(round(((t - t0) / MEAN_SYNODIC_MONTH) - (phi / 360.0)));
+    nth_new_moon((n..).find(|k| nth_new_moon(*k, dt) >= t).unwrap(), dt)
+}
+
+pub(crate) fn new_moon_before(t: f64, dt: &dyn DeltaT) -> f64 {
+    let t0 = nth_new_moon(0, dt);
+    let phi = lunar_phase(t, dt);
+    let n = i32(round(((t - t0) / MEAN_SYNODIC_MONTH) - (phi / 360.0)));
+    let mut k = n - 1;
+    while nth_new_moon(k + 1, dt) < t {
+        k += 1;
+    }
+    nth_new_moon(k, dt)
+}
+
+// Returns `n` such that `nth_new_moon(n)` is the last new moon at or before moment `t`, along
+// with `nth_new_moon(n)`, `nth_new_moon(n + 1)` and `lunar_phase(t)`
+pub(crate) fn new_moon_index_at_or_before(t: f64, dt: &dyn DeltaT) -> (i32, f64, f64, f64) {
+    let t0 = nth_new_moon(0, dt);
+    let phi = lunar_phase(t, dt);
+    let n = i32(round(((t - t0) / MEAN_SYNODIC_MONTH) - (phi / 360.0)));
+    let mut k = n - 1;
+    let mut new_moon = nth_new_moon(k, dt);
+    let mut next_new_moon = nth_new_moon(k + 1, dt);
+    while next_new_moon <= t {
+        k += 1;
+        new_moon = next_new_moon;
+        next_new_moon = nth_new_moon(k + 1, dt);
+    }
+    (k, new_moon, next_new_moon, phi)
 }
 
-fn lunar_longitude(t: f64) -> f64 {
-    let c = julian_centuries(t);
-    let mean_lunar_longitude = clamp_angle(polynomial(
+// Takes `julian_centuries(t)` instead of `t`
+fn mean_lunar_longitude(c: f64) -> f64 {
+    clamp_angle(polynomial(
         c,
         &[218.3164477, 481267.88123421, -0.0015786, 538841_f64.recip(), -(65194000_f64.recip())],
-    ));
-    let lunar_elongation = clamp_angle(polynomial(
+    ))
+}
+
+// Takes `julian_centuries(t)` instead of `t`
+fn lunar_elongation(c: f64) -> f64 {
+    clamp_angle(polynomial(
         c,
         &[297.8501921, 445267.1114034, -0.0018819, 545868_f64.recip(), -(113065000_f64.recip())],
-    ));
-    let solar_anomaly =
-        clamp_angle(polynomial(c, &[357.5291092, 35999.0502909, -0.0001536, 24490000_f64.recip()]));
-    let lunar_anomaly = clamp_angle(polynomial(
+    ))
+}
+
+// Takes `julian_centuries(t)` instead of `t`
+fn solar_anomaly(c: f64) -> f64 {
+    clamp_angle(polynomial(c, &[357.5291092, 35999.0502909, -0.0001536, 24490000_f64.recip()]))
+}
+
+// Takes `julian_centuries(t)` instead of `t`
+fn lunar_anomaly(c: f64) -> f64 {
+    clamp_angle(polynomial(
         c,
         &[134.9633964, 477198.8675055, 0.0087414, 69699_f64.recip(), -(14712000_f64.recip())],
-    ));
-    let moon_node = clamp_angle(polynomial(
+    ))
+}
+
+// Takes `julian_centuries(t)` instead of `t`
+fn moon_node(c: f64) -> f64 {
+    clamp_angle(polynomial(
         c,
         &[93.2720950, 483202.0175233, -0.0036539, -(3526000_f64.recip()), 863310000_f64.recip()],
-    ));
+    ))
+}
+
+// Sums one of the periodic lunar correction tables, using `func` (sine or cosine) on each
+// argument. Takes `julian_centuries(t)` instead of `t`.
+fn lunar_correction<F>(c: f64, table: &[(f64, f64, i32, f64, f64)], func: F) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let lunar_elongation = lunar_elongation(c);
+    let solar_anomaly = solar_anomaly(c);
+    let lunar_anomaly = lunar_anomaly(c);
+    let moon_node = moon_node(c);
     let e = polynomial(c, &[1.0, -0.002516, -0.0000074]);
-    let correction = sigma(&LUNAR_LONGITUDE_CORRECTION_TABLE, |(v, w, x, y, z)| {
-        v * e.powi(i32::abs(x))
-            * sin(w * lunar_elongation
-                + f64::from(x) * solar_anomaly
-                + y * lunar_anomaly
-                + z * moon_node)
-    }) / 1000000.0;
+    sigma(table, |(v, w, x, y, z)| {
+        v * powi(e, i32::abs(x))
+            * func(
+                w * lunar_elongation
+                    + f64::from(x) * solar_anomaly
+                    + y * lunar_anomaly
+                    + z * moon_node,
+            )
+    })
+}
+
+pub(crate) fn lunar_longitude(t: f64, dt: &dyn DeltaT) -> f64 {
+    let c = julian_centuries(t, dt);
+    let mean_lunar_longitude = mean_lunar_longitude(c);
+    let moon_node = moon_node(c);
+    let correction = lunar_correction(c, &LUNAR_LONGITUDE_CORRECTION_TABLE, sin) / 1000000.0;
     let venus = 0.003958 * sin(119.75 + c * 131.849);
     let jupiter = 0.000318 * sin(53.09 + c * 479264.29);
     let flat_earth = 0.001962 * sin(mean_lunar_longitude - moon_node);
     clamp_angle(mean_lunar_longitude + correction + venus + jupiter + flat_earth + nutation(c))
 }
 
-pub(crate) fn lunar_phase(t: f64) -> f64 {
-    let phi = clamp_angle(lunar_longitude(t) - solar_longitude(t));
-    let t0 = nth_new_moon(0);
-    let n = i32(((t - t0) / MEAN_SYNODIC_MONTH).round());
-    let phi_prime = 360.0 * ((t - nth_new_moon(n)) / MEAN_SYNODIC_MONTH).rem_euclid(1.0);
+fn lunar_latitude(t: f64, dt: &dyn DeltaT) -> f64 {
+    let c = julian_centuries(t, dt);
+    let mean_lunar_longitude = mean_lunar_longitude(c);
+    let lunar_anomaly = lunar_anomaly(c);
+    let moon_node = moon_node(c);
+    let latitude = lunar_correction(c, &LUNAR_LATITUDE_CORRECTION_TABLE, sin) / 1000000.0;
+    let venus = (175.0 / 1000000.0)
+        * (sin(119.75 + c * 131.849 + moon_node) + sin(119.75 + c * 131.849 - moon_node));
+    let flat_earth = (-2235.0 * sin(mean_lunar_longitude)
+        + 127.0 * sin(mean_lunar_longitude - lunar_anomaly)
+        - 115.0 * sin(mean_lunar_longitude + lunar_anomaly))
+        / 1000000.0;
+    let extra = (382.0 / 1000000.0) * sin(313.45 + c * 481266.484);
+    latitude + venus + flat_earth + extra
+}
+
+// Distance from the center of the Earth to the center of the Moon, in meters
+pub(crate) fn lunar_distance(t: f64, dt: &dyn DeltaT) -> f64 {
+    let c = julian_centuries(t, dt);
+    385000560.0 + lunar_correction(c, &LUNAR_DISTANCE_CORRECTION_TABLE, cos)
+}
+
+pub(crate) fn lunar_phase(t: f64, dt: &dyn DeltaT) -> f64 {
+    let phi = clamp_angle(lunar_longitude(t, dt) - solar_longitude(t, dt));
+    let t0 = nth_new_moon(0, dt);
+    let n = i32(round((t - t0) / MEAN_SYNODIC_MONTH));
+    let phi_prime = 360.0 * rem_euclid((t - nth_new_moon(n, dt)) / MEAN_SYNODIC_MONTH, 1.0);
     if (phi - phi_prime).abs() > 180.0 {
         phi_prime
     } else {
@@ -259,21 +464,203 @@ pub(crate) fn lunar_phase(t: f64) -> f64 {
     }
 }
 
-pub(crate) fn lunar_phase_at_or_before(phase: f64, t: f64) -> f64 {
-    let tau = t - MEAN_SYNODIC_MONTH / 360.0 * clamp_angle(lunar_phase(t) - phase);
-    inv_angle(lunar_phase, phase, tau - 2.0, t.min(tau + 2.0))
+pub(crate) fn lunar_phase_at_or_before(phase: f64, t: f64, dt: &dyn DeltaT) -> f64 {
+    let tau = t - MEAN_SYNODIC_MONTH / 360.0 * clamp_angle(lunar_phase(t, dt) - phase);
+    inv_angle(|x| lunar_phase(x, dt), phase, tau - 2.0, t.min(tau + 2.0))
+}
+
+pub(crate) fn lunar_phase_at_or_after(phase: f64, t: f64, dt: &dyn DeltaT) -> f64 {
+    let tau = t + MEAN_SYNODIC_MONTH / 360.0 * clamp_angle(phase - lunar_phase(t, dt));
+    inv_angle(|x| lunar_phase(x, dt), phase, t.max(tau - 2.0), tau + 2.0)
+}
+
+fn lunar_altitude(t: f64, location: Location, dt: &dyn DeltaT) -> f64 {
+    altitude(t, lunar_latitude(t, dt), lunar_longitude(t, dt), location, dt)
+}
+
+fn lunar_parallax(t: f64, location: Location, dt: &dyn DeltaT) -> f64 {
+    let geo = lunar_altitude(t, location, dt);
+    let cap_delta = lunar_distance(t, dt);
+    let alt = 6378140.0 / cap_delta;
+    let arg = alt * cos(geo);
+    arcsin(arg)
+}
+
+fn topocentric_lunar_altitude(t: f64, location: Location, dt: &dyn DeltaT) -> f64 {
+    lunar_altitude(t, location, dt) - lunar_parallax(t, location, dt)
 }
 
-pub(crate) fn lunar_phase_at_or_after(phase: f64, t: f64) -> f64 {
-    let tau = t + MEAN_SYNODIC_MONTH / 360.0 * clamp_angle(phase - lunar_phase(t));
-    inv_angle(lunar_phase, phase, t.max(tau - 2.0), tau + 2.0)
+// Apparent altitude of the upper limb of the Moon
+fn observed_lunar_altitude(t: f64, location: Location, dt: &dyn DeltaT) -> f64 {
+    topocentric_lunar_altitude(t, location, dt) + refraction(location) + 16.0 / 60.0
+}
+
+// First moonrise in [start, end)
+pub(crate) fn moonrise(start: f64, end: f64, location: Location, dt: &dyn DeltaT) -> Option<f64> {
+    find_crossing(|t| observed_lunar_altitude(t, location, dt), start, end, RISE_SET_STEP, true)
+}
+
+// First moonset in [start, end)
+pub(crate) fn moonset(start: f64, end: f64, location: Location, dt: &dyn DeltaT) -> Option<f64> {
+    find_crossing(|t| observed_lunar_altitude(t, location, dt), start, end, RISE_SET_STEP, false)
+}
+
+// =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=
+
+// The moment local mean time midnight begins `date` at `location`
+fn local_midnight(date: i32, location: Location) -> f64 {
+    f64::from(date) - location.longitude() / 360.0
+}
+
+// Angular separation of the centers of the Sun and Moon
+fn arc_of_light(t: f64, dt: &dyn DeltaT) -> f64 {
+    arccos(cos(lunar_latitude(t, dt)) * cos(lunar_phase(t, dt)))
+}
+
+// Topocentric width of the lit crescent, in arcminutes
+fn crescent_width(t: f64, location: Location, dt: &dyn DeltaT) -> f64 {
+    let parallax = arcsin(6378140.0 / lunar_distance(t, dt));
+    let semi_diameter = 0.27245 * parallax * 60.0;
+    let topocentric = semi_diameter * (1.0 + sin(lunar_altitude(t, location, dt)) * sin(parallax));
+    topocentric * (1.0 - cos(arc_of_light(t, dt)))
+}
+
+// Bruin's best time to look for the crescent on the evening of `date`, four-ninths of the way from
+// sunset to moonset
+fn bruin_best_view(date: i32, location: Location, dt: &dyn DeltaT) -> Option<f64> {
+    let start = local_midnight(date, location);
+    let sun = sunset(start, start + 1.0, location, dt)?;
+    let moon = moonset(sun, sun + 0.5, location, dt)?;
+    Some(sun + (moon - sun) * 4.0 / 9.0)
+}
+
+// Shaukat's criterion for seeing the crescent on the evening of `date`, as used by Calendrical
+// Calculations
+pub(crate) fn shaukat_visible_crescent(date: i32, location: Location, dt: &dyn DeltaT) -> bool {
+    let start = local_midnight(date, location);
+    if let Some(t) = dusk(start, start + 1.0, location, 4.5, dt) {
+        let phase = lunar_phase(t, dt);
+        0.0 < phase
+            && phase < 90.0
+            && (10.6..=90.0).contains(&arc_of_light(t, dt))
+            && lunar_altitude(t, location, dt) > 4.1
+    } else {
+        false
+    }
+}
+
+// Yallop's criterion (categories A and B) for seeing the crescent with the naked eye on the
+// evening of `date`
+pub(crate) fn yallop_visible_crescent(date: i32, location: Location, dt: &dyn DeltaT) -> bool {
+    if let Some(t) = bruin_best_view(date, location, dt) {
+        let phase = lunar_phase(t, dt);
+        let arcv = lunar_altitude(t, location, dt) - solar_altitude(t, location, dt);
+        let w = crescent_width(t, location, dt);
+        let q = (arcv - polynomial(w, &[11.8371, -6.3226, 0.7319, -0.1018])) / 10.0;
+        0.0 < phase && phase < 90.0 && q > -0.014
+    } else {
+        false
+    }
+}
+
+// Odeh's criterion (zone A) for seeing the crescent with the naked eye on the evening of `date`
+pub(crate) fn odeh_visible_crescent(date: i32, location: Location, dt: &dyn DeltaT) -> bool {
+    if let Some(t) = bruin_best_view(date, location, dt) {
+        let phase = lunar_phase(t, dt);
+        let arcv = topocentric_lunar_altitude(t, location, dt) - solar_altitude(t, location, dt);
+        let w = crescent_width(t, location, dt);
+        let v = arcv - polynomial(w, &[7.1651, -6.3226, 0.7319, -0.1018]);
+        0.0 < phase && phase < 90.0 && v >= 5.65
+    } else {
+        false
+    }
+}
+
+// The Babylonian criterion for seeing the crescent on the evening of `date`: the Moon is at least
+// a day old at sunset and sets at least 48 minutes after the Sun
+pub(crate) fn babylonian_visible_crescent(date: i32, location: Location, dt: &dyn DeltaT) -> bool {
+    let start = local_midnight(date, location);
+    if let Some(sun) = sunset(start, start + 1.0, location, dt) {
+        let phase = lunar_phase(sun, dt);
+        0.0 < phase
+            && phase < 90.0
+            && new_moon_before(sun, dt) <= sun - 1.0
+            && matches!(moonset(sun, sun + 0.5, location, dt), Some(moon) if moon - sun > 48.0 / 1440.0)
+    } else {
+        false
+    }
+}
+
+// The last date at or before `date` that began at an evening when the crescent was visible
+// according to `visible`, which takes the date of the evening; None if the crescent is not visible
+// in the two months searched (such as near the poles)
+pub(crate) fn phasis_on_or_before<F>(date: i32, visible: F, dt: &dyn DeltaT) -> Option<i32>
+where
+    F: Fn(i32) -> bool,
+{
+    let moon = i32(floor(lunar_phase_at_or_before(0.0, f64::from(date), dt)));
+    let age = date - moon;
+    let tau = if age <= 3 && !visible(date - 1) { moon - 30 } else { moon };
+    (tau..tau + 60).find(|d| visible(d - 1))
+}
+
+// The first lunar perigee or apogee found stepping from `t` by `step` days; true if perigee
+fn lunar_apsis(t: f64, step: f64, dt: &dyn DeltaT) -> (bool, f64) {
+    let slope = |x: f64| lunar_distance(x + 0.001, dt) - lunar_distance(x - 0.001, dt);
+    let mut a = t;
+    let mut slope_a = slope(a);
+    loop {
+        let b = a + step;
+        let slope_b = slope(b);
+        if (slope_a > 0.0) != (slope_b > 0.0) {
+            let perigee = (slope_a < 0.0) == (step > 0.0);
+            return (perigee, find_zero(slope, a.min(b), a.max(b)));
+        }
+        a = b;
+        slope_a = slope_b;
+    }
+}
+
+pub(crate) fn lunar_apsis_at_or_after(t: f64, dt: &dyn DeltaT) -> (bool, f64) {
+    lunar_apsis(t, 1.0, dt)
+}
+
+pub(crate) fn lunar_apsis_at_or_before(t: f64, dt: &dyn DeltaT) -> (bool, f64) {
+    lunar_apsis(t, -1.0, dt)
+}
+
+pub(crate) fn lunar_illumination(t: f64, dt: &dyn DeltaT) -> f64 {
+    let lambda = lunar_longitude(t, dt);
+    let beta = lunar_latitude(t, dt);
+    let psi = arccos(cos(beta) * cos(lambda - solar_longitude(t, dt)));
+    let cap_r = solar_distance(t, dt);
+    let i = arctan(cap_r * sin(psi), lunar_distance(t, dt) - cap_r * cos(psi));
+    (1.0 + cos(i)) * 0.5
+}
+
+// Position angle of the midpoint of the Moon's bright limb, measured eastward from north
+pub(crate) fn bright_limb_angle(t: f64, dt: &dyn DeltaT) -> f64 {
+    let lambda0 = solar_longitude(t, dt);
+    let alpha0 = right_ascension(t, 0.0, lambda0, dt);
+    let delta0 = declination(t, 0.0, lambda0, dt);
+    let lambda = lunar_longitude(t, dt);
+    let beta = lunar_latitude(t, dt);
+    let alpha = right_ascension(t, beta, lambda, dt);
+    let delta = declination(t, beta, lambda, dt);
+    arctan(
+        cos(delta0) * sin(alpha0 - alpha),
+        sin(delta0) * cos(delta) - cos(delta0) * sin(delta) * cos(alpha0 - alpha),
+    )
 }
 
 // =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=
 
 #[cfg(test)]
+#[allow(clippy::cast_possible_truncation)]
 #[test]
 fn test_sample_data() {
+    use crate::delta_t::EspenakMeeus;
+
     // Floating-point sample data is truncated at 6 decimal digits.
     macro_rules! assert_feq {
         ($a:expr, $b:expr) => {
@@ -281,17 +668,51 @@ fn test_sample_data() {
         };
     }
 
+    let dt = &EspenakMeeus;
     for (rd, (y, m, d), ephem, solar_l, lunar_l, new_moon_test) in TEST_DATA.iter().copied() {
         assert_eq!(gregorian_from_fixed(rd), (y, m, d));
         assert_eq!(fixed_from_gregorian(y, m, d), i32(rd));
-        assert_feq!(ephemeris_correction(rd), ephem);
-        assert_feq!(solar_longitude(rd + 0.5), solar_l);
-        assert_feq!(lunar_longitude(rd), lunar_l);
-        let new_moon = new_moon_at_or_after(rd);
+        assert_feq!(ephemeris_correction(rd, dt), ephem);
+        assert_feq!(solar_longitude(rd + 0.5, dt), solar_l);
+        assert_feq!(lunar_longitude(rd, dt), lunar_l);
+        let new_moon = new_moon_at_or_after(rd, dt);
         assert_feq!(new_moon, new_moon_test);
-        assert!((lunar_phase_at_or_before(0.0, new_moon + 0.001) - new_moon).abs() < 2e-5);
-        assert!((lunar_phase_at_or_before(0.0, rd + MEAN_SYNODIC_MONTH) - new_moon).abs() < 2e-5);
-        assert!((lunar_phase_at_or_after(0.0, new_moon - 0.001) - new_moon).abs() < 2e-5);
-        assert!((lunar_phase_at_or_after(0.0, rd) - new_moon).abs() < 2e-5);
+        assert!((lunar_phase_at_or_before(0.0, new_moon + 0.001, dt) - new_moon).abs() < 2e-5);
+        assert!(
+            (lunar_phase_at_or_before(0.0, rd + MEAN_SYNODIC_MONTH, dt) - new_moon).abs() < 2e-5
+        );
+        assert!((lunar_phase_at_or_after(0.0, new_moon - 0.001, dt) - new_moon).abs() < 2e-5);
+        assert!((lunar_phase_at_or_after(0.0, rd, dt) - new_moon).abs() < 2e-5);
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_lunar_position() {
+    use crate::delta_t::EspenakMeeus;
+
+    // Meeus, Astronomical Algorithms, 2nd ed., examples 12.a, 22.a, and 47.a
+    let dt = &EspenakMeeus;
+    let t = f64::from(fixed_from_gregorian(1987, 4, 10));
+    assert!((sidereal_from_moment(t) - 197.693195).abs() < 1e-6);
+    assert!((obliquity(universal_from_dynamical(t, dt), dt) - 23.440946).abs() < 1e-6);
+
+    let t = f64::from(fixed_from_gregorian(1992, 4, 12));
+    let t = universal_from_dynamical(t, dt);
+    assert!((lunar_longitude(t, dt) - 133.167265).abs() < 1e-4);
+    assert!((lunar_latitude(t, dt) - -3.229126).abs() < 1e-4);
+    assert!((lunar_distance(t, dt) - 368409700.0).abs() < 100.0);
+}
+
+#[cfg(all(test, feature = "deterministic"))]
+#[test]
+fn test_deterministic() {
+    use crate::data::DETERMINISTIC_DATA;
+    use crate::delta_t::EspenakMeeus;
+
+    let dt = &EspenakMeeus;
+    for (rd, phase, first_quarter) in DETERMINISTIC_DATA.iter().copied() {
+        assert_eq!(lunar_phase(rd, dt).to_bits(), phase);
+        assert_eq!(lunar_phase_at_or_after(90.0, rd, dt).to_bits(), first_quarter);
     }
 }
@@ -0,0 +1,619 @@
+// Copyright (c) 2018 Edward M. Reingold and Nachum Dershowitz
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+//
+// Functions from chapter 19, "The Chinese Calendar", of:
+//
+//     E. M. Reingold and N. Dershowitz, Calendrical Calculations: The Ultimate Edition.
+//         Cambridge University Press, 2018. doi:10.1017/9781107415058
+//
+// See `calendar.rs` for the representation of dates and moments.
+
+#![allow(clippy::unreadable_literal)]
+
+use crate::calendar::{
+    estimate_prior_solar_longitude, fixed_from_gregorian, gregorian_year_from_fixed,
+    new_moon_at_or_after, new_moon_before, solar_longitude, solar_longitude_after,
+    solar_longitude_before, MEAN_SYNODIC_MONTH, MEAN_TROPICAL_YEAR,
+};
+use crate::delta_t::{DeltaT, EspenakMeeus};
+use crate::iter::{moment_bounds, Angle, AngleIter};
+use crate::moment::{CivilDate, Day, FixedDate, Moment, Timestamp};
+use crate::util::{amod, clamp_angle, floor, i32, nonneg, round};
+use core::iter::FusedIterator;
+use core::ops::RangeBounds;
+
+// fixed_from_gregorian(-2636, 2, 15)
+const CHINESE_EPOCH: i32 = -963099;
+const CHINESE_MONTH_NAME_EPOCH: i32 = 57;
+const CHINESE_DAY_NAME_EPOCH: i32 = 45;
+
+// The offset of Beijing's time zone from UTC, in days. Before 1929, the local mean time of Beijing
+// was used.
+fn chinese_zone(t: f64) -> f64 {
+    if gregorian_year_from_fixed(t) < 1929 {
+        1397.0 / 180.0 / 24.0
+    } else {
+        8.0 / 24.0
+    }
+}
+
+fn midnight_in_china(date: i32) -> f64 {
+    let date = f64::from(date);
+    date - chinese_zone(date)
+}
+
+fn current_major_solar_term(date: i32, dt: &dyn DeltaT) -> i32 {
+    let s = solar_longitude(midnight_in_china(date), dt);
+    amod(2 + i32(floor(s / 30.0)), 12)
+}
+
+fn chinese_winter_solstice_on_or_before(date: i32, dt: &dyn DeltaT) -> i32 {
+    let approx = estimate_prior_solar_longitude(270.0, midnight_in_china(date + 1), dt);
+    let mut day = i32(floor(approx)) - 1;
+    while solar_longitude(midnight_in_china(day + 1), dt) <= 270.0 {
+        day += 1;
+    }
+    day
+}
+
+fn chinese_new_moon_on_or_after(date: i32, dt: &dyn DeltaT) -> i32 {
+    let t = new_moon_at_or_after(midnight_in_china(date), dt);
+    i32(floor(t + chinese_zone(t)))
+}
+
+fn chinese_new_moon_before(date: i32, dt: &dyn DeltaT) -> i32 {
+    let t = new_moon_before(midnight_in_china(date), dt);
+    i32(floor(t + chinese_zone(t)))
+}
+
+fn chinese_no_major_solar_term(date: i32, dt: &dyn DeltaT) -> bool {
+    current_major_solar_term(date, dt)
+        == current_major_solar_term(chinese_new_moon_on_or_after(date + 1, dt), dt)
+}
+
+fn chinese_prior_leap_month(m_prime: i32, mut m: i32, dt: &dyn DeltaT) -> bool {
+    while m >= m_prime {
+        if chinese_no_major_solar_term(m, dt) {
+            return true;
+        }
+        m = chinese_new_moon_before(m, dt);
+    }
+    false
+}
+
+fn chinese_new_year_in_sui(date: i32, dt: &dyn DeltaT) -> i32 {
+    let s1 = chinese_winter_solstice_on_or_before(date, dt);
+    let s2 = chinese_winter_solstice_on_or_before(s1 + 370, dt);
+    let m12 = chinese_new_moon_on_or_after(s1 + 1, dt);
+    let m13 = chinese_new_moon_on_or_after(m12 + 1, dt);
+    let next_m11 = chinese_new_moon_before(s2 + 1, dt);
+    if i32(round(f64::from(next_m11 - m12) / MEAN_SYNODIC_MONTH)) == 12
+        && (chinese_no_major_solar_term(m12, dt) || chinese_no_major_solar_term(m13, dt))
+    {
+        chinese_new_moon_on_or_after(m13 + 1, dt)
+    } else {
+        m13
+    }
+}
+
+fn chinese_new_year_on_or_before(date: i32, dt: &dyn DeltaT) -> i32 {
+    let new_year = chinese_new_year_in_sui(date, dt);
+    if date >= new_year {
+        new_year
+    } else {
+        chinese_new_year_in_sui(date - 180, dt)
+    }
+}
+
+fn chinese_from_fixed(date: i32, dt: &dyn DeltaT) -> ChineseDate {
+    let s1 = chinese_winter_solstice_on_or_before(date, dt);
+    let s2 = chinese_winter_solstice_on_or_before(s1 + 370, dt);
+    let m12 = chinese_new_moon_on_or_after(s1 + 1, dt);
+    let next_m11 = chinese_new_moon_before(s2 + 1, dt);
+    let m = chinese_new_moon_before(date + 1, dt);
+    let leap_year = i32(round(f64::from(next_m11 - m12) / MEAN_SYNODIC_MONTH)) == 12;
+    let month = amod(
+        i32(round(f64::from(m - m12) / MEAN_SYNODIC_MONTH))
+            - i32::from(leap_year && chinese_prior_leap_month(m12, m, dt)),
+        12,
+    );
+    let leap_month = leap_year
+        && chinese_no_major_solar_term(m, dt)
+        && !chinese_prior_leap_month(m12, chinese_new_moon_before(m, dt), dt);
+    let elapsed_years = i32(floor(
+        1.5 - f64::from(month) / 12.0 + f64::from(date - CHINESE_EPOCH) / MEAN_TROPICAL_YEAR,
+    ));
+    ChineseDate {
+        cycle: (elapsed_years - 1).div_euclid(60) + 1,
+        year: nonneg(amod(elapsed_years, 60)),
+        month: nonneg(month),
+        leap: leap_month,
+        day: nonneg(date - m + 1),
+    }
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn fixed_from_chinese(date: ChineseDate, dt: &dyn DeltaT) -> i32 {
+    let mid_year = i32(floor(
+        f64::from(CHINESE_EPOCH)
+            + (f64::from(date.cycle - 1) * 60.0 + f64::from(date.year) - 1.0 + 0.5)
+                * MEAN_TROPICAL_YEAR,
+    ));
+    let new_year = chinese_new_year_on_or_before(mid_year, dt);
+    let p = chinese_new_moon_on_or_after(new_year + (date.month as i32 - 1) * 29, dt);
+    let d = chinese_from_fixed(p, dt);
+    let prior_new_moon = if date.month == d.month && date.leap == d.leap {
+        p
+    } else {
+        chinese_new_moon_on_or_after(p + 1, dt)
+    };
+    prior_new_moon + date.day as i32 - 1
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn sexagesimal_name(n: i32) -> (CelestialStem, EarthlyBranch) {
+    (CELESTIAL_STEMS[amod(n, 10) as usize - 1], EARTHLY_BRANCHES[amod(n, 12) as usize - 1])
+}
+
+// =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=
+
+/// A date in the Chinese lunisolar calendar.
+///
+/// Years are counted in 60-year cycles, beginning in 2637 BCE. Months begin on the day of a new
+/// moon in Beijing; in years with thirteen months, the first month that contains no major solar
+/// term is a leap month, which has the same number as the month before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChineseDate {
+    /// The 60-year cycle.
+    pub cycle: i32,
+    /// The year within the cycle, from 1 to 60.
+    pub year: u32,
+    /// The month, from 1 to 12.
+    pub month: u32,
+    /// Whether the month is a leap month.
+    pub leap: bool,
+    /// The day of the month, from 1 to 30.
+    pub day: u32,
+}
+
+impl ChineseDate {
+    /// Converts a date in the proleptic Gregorian calendar to the Chinese calendar.
+    ///
+    /// ```
+    /// use esbat::{ChineseDate, FixedDate};
+    ///
+    /// let date = ChineseDate::from_gregorian(FixedDate::from_gregorian(2020, 6, 1).unwrap());
+    /// assert_eq!(
+    ///     date,
+    ///     ChineseDate { cycle: 78, year: 37, month: 4, leap: true, day: 10 }
+    /// );
+    /// ```
+    pub fn from_gregorian<D: Day>(date: D) -> ChineseDate {
+        ChineseDate::from_gregorian_with(date, &EspenakMeeus)
+    }
+
+    pub(crate) fn from_gregorian_with<D: Day>(date: D, dt: &dyn DeltaT) -> ChineseDate {
+        chinese_from_fixed(date.fixed_date().rd(), dt)
+    }
+
+    /// Converts the date to the proleptic Gregorian calendar.
+    ///
+    /// Returns `None` if the date cannot be represented by `D`.
+    ///
+    /// ```
+    /// use esbat::{ChineseDate, FixedDate};
+    ///
+    /// let date = ChineseDate { cycle: 78, year: 37, month: 8, leap: false, day: 15 };
+    /// assert_eq!(date.to_gregorian(), Some(FixedDate::from_gregorian(2020, 10, 1).unwrap()));
+    /// ```
+    pub fn to_gregorian<D: CivilDate>(self) -> Option<D> {
+        self.to_gregorian_with(&EspenakMeeus)
+    }
+
+    pub(crate) fn to_gregorian_with<D: CivilDate>(self, dt: &dyn DeltaT) -> Option<D> {
+        D::from_fixed(FixedDate::from_rd(fixed_from_chinese(self, dt)))
+    }
+
+    /// Returns the sexagenary name of the year.
+    ///
+    /// ```
+    /// use esbat::{CelestialStem, ChineseDate, EarthlyBranch};
+    ///
+    /// let date = ChineseDate { cycle: 78, year: 37, month: 1, leap: false, day: 1 };
+    /// assert_eq!(date.year_name(), (CelestialStem::Geng, EarthlyBranch::Zi));
+    /// ```
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn year_name(self) -> (CelestialStem, EarthlyBranch) {
+        sexagesimal_name(self.year as i32)
+    }
+
+    /// Returns the sexagenary name of the month.
+    ///
+    /// Leap months have the same name as the month before them.
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn month_name(self) -> (CelestialStem, EarthlyBranch) {
+        let elapsed_months = 12 * (self.year as i32 - 1) + (self.month as i32 - 1);
+        sexagesimal_name(elapsed_months - CHINESE_MONTH_NAME_EPOCH)
+    }
+
+    /// Returns the sexagenary name of the day.
+    pub fn day_name(self) -> (CelestialStem, EarthlyBranch) {
+        self.day_name_with(&EspenakMeeus)
+    }
+
+    pub(crate) fn day_name_with(self, dt: &dyn DeltaT) -> (CelestialStem, EarthlyBranch) {
+        sexagesimal_name(fixed_from_chinese(self, dt) - CHINESE_DAY_NAME_EPOCH)
+    }
+}
+
+/// Returns the date of the Chinese New Year (the Spring Festival) in a Gregorian year.
+///
+/// Returns `None` if the date cannot be represented by `D`.
+///
+/// ```
+/// use esbat::FixedDate;
+///
+/// let date = FixedDate::from_gregorian(2020, 1, 25).unwrap();
+/// assert_eq!(esbat::chinese_new_year(2020), Some(date));
+/// ```
+pub fn chinese_new_year<D: CivilDate>(year: i32) -> Option<D> {
+    chinese_new_year_with(year, &EspenakMeeus)
+}
+
+pub(crate) fn chinese_new_year_with<D: CivilDate>(year: i32, dt: &dyn DeltaT) -> Option<D> {
+    D::from_fixed(FixedDate::from_rd(chinese_new_year_on_or_before(
+        fixed_from_gregorian(year, 7, 1),
+        dt,
+    )))
+}
+
+/// Returns the date of the Mid-Autumn Festival (the 15th day of the 8th month of the Chinese
+/// calendar) in a Gregorian year.
+///
+/// Returns `None` if the date cannot be represented by `D`.
+///
+/// ```
+/// use esbat::FixedDate;
+///
+/// let date = FixedDate::from_gregorian(2020, 10, 1).unwrap();
+/// assert_eq!(esbat::mid_autumn_festival(2020), Some(date));
+/// ```
+pub fn mid_autumn_festival<D: CivilDate>(year: i32) -> Option<D> {
+    mid_autumn_festival_with(year, &EspenakMeeus)
+}
+
+pub(crate) fn mid_autumn_festival_with<D: CivilDate>(year: i32, dt: &dyn DeltaT) -> Option<D> {
+    let new_year =
+        chinese_from_fixed(chinese_new_year_on_or_before(fixed_from_gregorian(year, 7, 1), dt), dt);
+    ChineseDate { month: 8, day: 15, ..new_year }.to_gregorian_with(dt)
+}
+
+/// The ten celestial stems (<i>tiangan</i>) of the sexagenary cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CelestialStem {
+    /// 甲, yang wood.
+    Jia,
+    /// 乙, yin wood.
+    Yi,
+    /// 丙, yang fire.
+    Bing,
+    /// 丁, yin fire.
+    Ding,
+    /// 戊, yang earth.
+    Wu,
+    /// 己, yin earth.
+    Ji,
+    /// 庚, yang metal.
+    Geng,
+    /// 辛, yin metal.
+    Xin,
+    /// 壬, yang water.
+    Ren,
+    /// 癸, yin water.
+    Gui,
+}
+
+const CELESTIAL_STEMS: [CelestialStem; 10] = [
+    CelestialStem::Jia,
+    CelestialStem::Yi,
+    CelestialStem::Bing,
+    CelestialStem::Ding,
+    CelestialStem::Wu,
+    CelestialStem::Ji,
+    CelestialStem::Geng,
+    CelestialStem::Xin,
+    CelestialStem::Ren,
+    CelestialStem::Gui,
+];
+
+/// The twelve earthly branches (<i>dizhi</i>) of the sexagenary cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EarthlyBranch {
+    /// 子, the rat.
+    Zi,
+    /// 丑, the ox.
+    Chou,
+    /// 寅, the tiger.
+    Yin,
+    /// 卯, the rabbit.
+    Mao,
+    /// 辰, the dragon.
+    Chen,
+    /// 巳, the snake.
+    Si,
+    /// 午, the horse.
+    Wu,
+    /// 未, the goat.
+    Wei,
+    /// 申, the monkey.
+    Shen,
+    /// 酉, the rooster.
+    You,
+    /// 戌, the dog.
+    Xu,
+    /// 亥, the pig.
+    Hai,
+}
+
+const EARTHLY_BRANCHES: [EarthlyBranch; 12] = [
+    EarthlyBranch::Zi,
+    EarthlyBranch::Chou,
+    EarthlyBranch::Yin,
+    EarthlyBranch::Mao,
+    EarthlyBranch::Chen,
+    EarthlyBranch::Si,
+    EarthlyBranch::Wu,
+    EarthlyBranch::Wei,
+    EarthlyBranch::Shen,
+    EarthlyBranch::You,
+    EarthlyBranch::Xu,
+    EarthlyBranch::Hai,
+];
+
+// =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=
+
+/// The 24 solar terms (<i>jieqi</i>), which divide the year by solar longitude.
+///
+/// The terms at multiples of 30&deg; (beginning with Yushui) are the major solar terms
+/// (<i>zhongqi</i>) that determine the months of the Chinese calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SolarTerm {
+    /// 立春, "Start of Spring", at 315&deg;.
+    Lichun,
+    /// 雨水, "Rain Water", at 330&deg;.
+    Yushui,
+    /// 驚蟄, "Awakening of Insects", at 345&deg;.
+    Jingzhe,
+    /// 春分, "Spring Equinox", at 0&deg;.
+    Chunfen,
+    /// 清明, "Clear and Bright", at 15&deg;.
+    Qingming,
+    /// 穀雨, "Grain Rain", at 30&deg;.
+    Guyu,
+    /// 立夏, "Start of Summer", at 45&deg;.
+    Lixia,
+    /// 小滿, "Grain Buds", at 60&deg;.
+    Xiaoman,
+    /// 芒種, "Grain in Ear", at 75&deg;.
+    Mangzhong,
+    /// 夏至, "Summer Solstice", at 90&deg;.
+    Xiazhi,
+    /// 小暑, "Minor Heat", at 105&deg;.
+    Xiaoshu,
+    /// 大暑, "Major Heat", at 120&deg;.
+    Dashu,
+    /// 立秋, "Start of Autumn", at 135&deg;.
+    Liqiu,
+    /// 處暑, "End of Heat", at 150&deg;.
+    Chushu,
+    /// 白露, "White Dew", at 165&deg;.
+    Bailu,
+    /// 秋分, "Autumn Equinox", at 180&deg;.
+    Qiufen,
+    /// 寒露, "Cold Dew", at 195&deg;.
+    Hanlu,
+    /// 霜降, "Frost's Descent", at 210&deg;.
+    Shuangjiang,
+    /// 立冬, "Start of Winter", at 225&deg;.
+    Lidong,
+    /// 小雪, "Minor Snow", at 240&deg;.
+    Xiaoxue,
+    /// 大雪, "Major Snow", at 255&deg;.
+    Daxue,
+    /// 冬至, "Winter Solstice", at 270&deg;.
+    Dongzhi,
+    /// 小寒, "Minor Cold", at 285&deg;.
+    Xiaohan,
+    /// 大寒, "Major Cold", at 300&deg;.
+    Dahan,
+}
+
+const SOLAR_TERMS: [SolarTerm; 24] = [
+    SolarTerm::Lichun,
+    SolarTerm::Yushui,
+    SolarTerm::Jingzhe,
+    SolarTerm::Chunfen,
+    SolarTerm::Qingming,
+    SolarTerm::Guyu,
+    SolarTerm::Lixia,
+    SolarTerm::Xiaoman,
+    SolarTerm::Mangzhong,
+    SolarTerm::Xiazhi,
+    SolarTerm::Xiaoshu,
+    SolarTerm::Dashu,
+    SolarTerm::Liqiu,
+    SolarTerm::Chushu,
+    SolarTerm::Bailu,
+    SolarTerm::Qiufen,
+    SolarTerm::Hanlu,
+    SolarTerm::Shuangjiang,
+    SolarTerm::Lidong,
+    SolarTerm::Xiaoxue,
+    SolarTerm::Daxue,
+    SolarTerm::Dongzhi,
+    SolarTerm::Xiaohan,
+    SolarTerm::Dahan,
+];
+
+impl SolarTerm {
+    /// Views the 24 solar terms as an iterator, beginning with Lichun.
+    ///
+    /// ```
+    /// # use esbat::SolarTerm;
+    /// assert_eq!(SolarTerm::iter().next(), Some(&SolarTerm::Lichun));
+    /// assert_eq!(SolarTerm::iter().len(), 24);
+    /// ```
+    pub fn iter() -> core::slice::Iter<'static, SolarTerm> {
+        SOLAR_TERMS.iter()
+    }
+
+    /// Returns the solar longitude at which the solar term begins, in degrees.
+    ///
+    /// ```
+    /// # use esbat::SolarTerm;
+    /// assert_eq!(SolarTerm::Lichun.solar_longitude(), 315.0);
+    /// assert_eq!(SolarTerm::Chunfen.solar_longitude(), 0.0);
+    /// ```
+    pub fn solar_longitude(self) -> f64 {
+        clamp_angle(315.0 + 15.0 * f64::from(self.index()))
+    }
+
+    /// Returns true if the solar term is a major solar term (<i>zhongqi</i>).
+    ///
+    /// ```
+    /// # use esbat::SolarTerm;
+    /// assert!(SolarTerm::Dongzhi.is_major());
+    /// assert!(!SolarTerm::Lichun.is_major());
+    /// ```
+    pub fn is_major(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    fn index(self) -> u8 {
+        SOLAR_TERMS.iter().zip(0..).find(|(term, _)| **term == self).map_or(0, |(_, index)| index)
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn from_angle(angle: f64) -> SolarTerm {
+        debug_assert!((clamp_angle(angle) - angle).abs() < f64::EPSILON);
+        SOLAR_TERMS[(round(angle / 15.0) as usize + 3) % 24]
+    }
+}
+
+/// Returns an iterator of solar terms and their moments.
+///
+/// ```
+/// use esbat::{FixedDate, SolarTerm};
+///
+/// let start = FixedDate::from_gregorian(2020, 1, 1).unwrap().moment();
+/// let end = FixedDate::from_gregorian(2020, 3, 1).unwrap().moment();
+/// let mut iter = esbat::solar_term_iter(start..end);
+///
+/// assert_eq!(iter.next().unwrap().0, SolarTerm::Xiaohan);
+/// assert_eq!(iter.next().unwrap().0, SolarTerm::Dahan);
+/// let (term, t) = iter.next().unwrap();
+/// assert_eq!(term, SolarTerm::Lichun);
+/// assert_eq!(t.fixed_date(), FixedDate::from_gregorian(2020, 2, 4).unwrap());
+/// assert_eq!(iter.next().unwrap().0, SolarTerm::Yushui);
+/// assert!(iter.next().is_none());
+/// ```
+pub fn solar_term_iter<T, B>(range: B) -> SolarTermIter<T>
+where
+    T: Timestamp,
+    B: RangeBounds<T>,
+{
+    let (start, end) = moment_bounds(&range);
+    SolarTermIter::new(start, end, EspenakMeeus)
+}
+
+/// Solar term iterator.
+///
+/// This struct is created by [`solar_term_iter`] or
+/// [`Ephemeris::solar_term_iter`](crate::Ephemeris::solar_term_iter), which determines the ΔT model `M`.
+#[derive(Debug, Clone)]
+pub struct SolarTermIter<T = Moment, M = EspenakMeeus> {
+    inner: AngleIter<T, M>,
+}
+
+impl<T: Timestamp, M: DeltaT> SolarTermIter<T, M> {
+    pub(crate) fn new(start: (f64, bool), end: (f64, bool), model: M) -> SolarTermIter<T, M> {
+        SolarTermIter {
+            inner: AngleIter::new(
+                Angle {
+                    value: solar_longitude,
+                    at_or_after: solar_longitude_after,
+                    at_or_before: solar_longitude_before,
+                    step: 15.0,
+                    skip: 1.0,
+                },
+                start,
+                end,
+                model,
+            ),
+        }
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> Iterator for SolarTermIter<T, M> {
+    type Item = (SolarTerm, T);
+
+    fn next(&mut self) -> Option<(SolarTerm, T)> {
+        let (angle, next) = self.inner.next()?;
+        Some((SolarTerm::from_angle(angle), next))
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> FusedIterator for SolarTermIter<T, M> {}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_chinese() {
+    use chrono::NaiveDate;
+
+    let dt = &EspenakMeeus;
+    assert_eq!(CHINESE_EPOCH, fixed_from_gregorian(-2636, 2, 15));
+
+    let new_years = [
+        (2019, (2, 5)),
+        (2020, (1, 25)),
+        (2021, (2, 12)),
+        (2022, (2, 1)),
+        (2023, (1, 22)),
+        (2024, (2, 10)),
+        (2033, (1, 31)),
+    ];
+    for (year, (month, day)) in &new_years {
+        assert_eq!(
+            chinese_new_year(*year),
+            Some(NaiveDate::from_ymd_opt(*year, *month, *day).unwrap())
+        );
+    }
+
+    let mid_autumn = [(2020, (10, 1)), (2021, (9, 21)), (2022, (9, 10)), (2023, (9, 29))];
+    for (year, (month, day)) in &mid_autumn {
+        assert_eq!(
+            mid_autumn_festival(*year),
+            Some(NaiveDate::from_ymd_opt(*year, *month, *day).unwrap())
+        );
+    }
+
+    // 2023 has a leap second month
+    let date = ChineseDate::from_gregorian(NaiveDate::from_ymd_opt(2023, 3, 22).unwrap());
+    assert_eq!(date, ChineseDate { cycle: 78, year: 40, month: 2, leap: true, day: 1 });
+    assert_eq!(date.year_name(), (CelestialStem::Gui, EarthlyBranch::Mao));
+
+    let mut date = fixed_from_gregorian(2019, 1, 1);
+    while date < fixed_from_gregorian(2025, 1, 1) {
+        assert_eq!(fixed_from_chinese(chinese_from_fixed(date, dt), dt), date);
+        date += 5;
+    }
+
+    // The day name cycle is continuous; 2000-01-01 was a wuwu day
+    let date = ChineseDate::from_gregorian(NaiveDate::from_ymd_opt(2000, 1, 1).unwrap());
+    assert_eq!(date.day_name(), (CelestialStem::Wu, EarthlyBranch::Wu));
+}
@@ -6,23 +6,197 @@
 // or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
 
 use crate::calendar::{fixed_from_gregorian, gregorian_from_fixed};
-use crate::util::u32;
-use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
+use crate::moment::{
+    moment_from_parts, parts_from_moment, CivilDate, Day, FixedDate, LocalDate, Moment, Time,
+    Timestamp, Weekday,
+};
+#[cfg(feature = "compat-date")]
+#[allow(deprecated)]
+use chrono::Date;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike, Utc};
 
 pub(crate) fn fixed_from_chrono(t: DateTime<Utc>) -> f64 {
-    let rd_sec = f64::from(t.hour() * 3600 + t.minute() * 60 + t.second())
-        + (f64::from(t.nanosecond()) / 1_000_000_000.0);
-    f64::from(fixed_from_gregorian(t.year(), t.month(), t.day())) + (rd_sec / 86400.0)
+    let time = (t.hour(), t.minute(), t.second(), t.nanosecond());
+    moment_from_parts((t.year(), t.month(), t.day()), time).rd()
 }
 
 pub(crate) fn chrono_from_fixed(t: f64) -> Option<DateTime<Utc>> {
-    let (year, month, day) = gregorian_from_fixed(t);
-    let fract = t.rem_euclid(1.0) * 86400.0;
-    let hour = u32(fract / 3600.0);
-    let min = u32(fract % 3600.0 / 60.0);
-    let sec = u32(fract % 60.0);
-    let nano = u32((fract % 1.0) * 1_000_000_000.0);
-    Utc.ymd_opt(year, month, day).and_hms_nano_opt(hour, min, sec, nano).single()
+    let ((year, month, day), (hour, min, sec, nano)) = parts_from_moment(Moment::from_rd(t));
+    let date = NaiveDate::from_ymd_opt(year, month, day)?;
+    Some(Utc.from_utc_datetime(&date.and_hms_nano_opt(hour, min, sec, nano)?))
+}
+
+pub(crate) fn fixed_from_naive(date: NaiveDate) -> i32 {
+    fixed_from_gregorian(date.year(), date.month(), date.day())
+}
+
+pub(crate) fn naive_from_fixed(date: i32) -> Option<NaiveDate> {
+    let (year, month, day) = gregorian_from_fixed(f64::from(date));
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+// The first moment of `date` in `tz`. Midnight is skipped when a time zone springs forward at
+// midnight, in which case the day begins at the end of the gap.
+fn start_of_day<Tz: TimeZone>(date: NaiveDate, tz: &Tz) -> Option<f64> {
+    let midnight = date.and_hms_opt(0, 0, 0)?;
+    (0..=24 * 4)
+        .find_map(|i| tz.from_local_datetime(&(midnight + Duration::minutes(15 * i))).earliest())
+        .map(|t| fixed_from_chrono(t.with_timezone(&Utc)))
+}
+
+// The moments at which `date` begins and ends in `tz`, which are not necessarily 24 hours apart
+fn fixed_day_bounds<Tz: TimeZone>(date: NaiveDate, tz: &Tz) -> (f64, f64) {
+    let fixed = f64::from(fixed_from_naive(date));
+    let start = start_of_day(date, tz).unwrap_or(fixed);
+    // The last day Chrono can represent is assumed to be 24 hours long
+    let end = date.succ_opt().and_then(|next| start_of_day(next, tz)).unwrap_or(start + 1.0);
+    (start, end)
+}
+
+impl<Tz: TimeZone> Time for DateTime<Tz> {
+    fn to_moment(&self) -> Moment {
+        Moment::from_rd(fixed_from_chrono(self.with_timezone(&Utc)))
+    }
+
+    fn with_moment(&self, moment: Moment) -> Option<DateTime<Tz>> {
+        Some(chrono_from_fixed(moment.rd())?.with_timezone(&self.timezone()))
+    }
+
+    fn fixed_date(&self) -> FixedDate {
+        FixedDate::from_rd(fixed_from_naive(self.naive_local().date()))
+    }
+}
+
+impl Timestamp for DateTime<Utc> {
+    fn from_moment(moment: Moment) -> Option<DateTime<Utc>> {
+        chrono_from_fixed(moment.rd())
+    }
+
+    fn min_value() -> DateTime<Utc> {
+        DateTime::<Utc>::MIN_UTC
+    }
+
+    fn max_value() -> DateTime<Utc> {
+        DateTime::<Utc>::MAX_UTC
+    }
+}
+
+/// A `DateTime` is also the day it falls on in its time zone, from local midnight to local
+/// midnight. The day containing a moment is represented by that moment in the same time zone.
+impl<Tz: TimeZone> Day for DateTime<Tz> {
+    type Time = DateTime<Utc>;
+
+    fn fixed_date(&self) -> FixedDate {
+        Time::fixed_date(self)
+    }
+
+    fn bounds(&self) -> (Moment, Moment) {
+        let (start, end) = fixed_day_bounds(self.naive_local().date(), &self.timezone());
+        (Moment::from_rd(start), Moment::from_rd(end))
+    }
+
+    fn day_containing(&self, moment: Moment) -> Option<DateTime<Tz>> {
+        Time::with_moment(self, moment)
+    }
+}
+
+#[cfg(feature = "compat-date")]
+#[allow(deprecated)]
+impl<Tz: TimeZone> Day for Date<Tz> {
+    type Time = DateTime<Utc>;
+
+    fn fixed_date(&self) -> FixedDate {
+        FixedDate::from_rd(fixed_from_naive(self.naive_local()))
+    }
+
+    fn bounds(&self) -> (Moment, Moment) {
+        let (start, end) = fixed_day_bounds(self.naive_local(), &self.timezone());
+        (Moment::from_rd(start), Moment::from_rd(end))
+    }
+
+    fn day_containing(&self, moment: Moment) -> Option<Date<Tz>> {
+        Some(chrono_from_fixed(moment.rd())?.with_timezone(&self.timezone()).date())
+    }
+}
+
+#[cfg(feature = "compat-date")]
+#[allow(deprecated)]
+impl CivilDate for Date<Utc> {
+    fn from_fixed(date: FixedDate) -> Option<Date<Utc>> {
+        Some(Date::from_utc(naive_from_fixed(date.rd())?, Utc))
+    }
+
+    fn min_value() -> Date<Utc> {
+        Date::<Utc>::MIN_UTC
+    }
+
+    fn max_value() -> Date<Utc> {
+        Date::<Utc>::MAX_UTC
+    }
+}
+
+impl Day for NaiveDate {
+    type Time = DateTime<Utc>;
+
+    fn fixed_date(&self) -> FixedDate {
+        FixedDate::from_rd(fixed_from_naive(*self))
+    }
+
+    fn bounds(&self) -> (Moment, Moment) {
+        Day::bounds(&Day::fixed_date(self))
+    }
+
+    fn day_containing(&self, moment: Moment) -> Option<NaiveDate> {
+        naive_from_fixed(moment.fixed_date().rd())
+    }
+}
+
+impl CivilDate for NaiveDate {
+    fn from_fixed(date: FixedDate) -> Option<NaiveDate> {
+        naive_from_fixed(date.rd())
+    }
+
+    fn min_value() -> NaiveDate {
+        NaiveDate::MIN
+    }
+
+    fn max_value() -> NaiveDate {
+        NaiveDate::MAX
+    }
+}
+
+/// A `NaiveDate` in a Chrono `TimeZone` runs from local midnight to local midnight.
+impl<Tz: TimeZone> LocalDate<Tz> for NaiveDate {
+    type Local = DateTime<Tz>;
+
+    fn local_bounds(&self, zone: &Tz) -> (Moment, Moment) {
+        let (start, end) = fixed_day_bounds(*self, zone);
+        (Moment::from_rd(start), Moment::from_rd(end))
+    }
+
+    fn local_time(zone: &Tz, moment: Moment) -> Option<DateTime<Tz>> {
+        Some(chrono_from_fixed(moment.rd())?.with_timezone(zone))
+    }
+}
+
+impl From<chrono::Weekday> for Weekday {
+    fn from(weekday: chrono::Weekday) -> Weekday {
+        crate::moment::WEEKDAYS[weekday.num_days_from_sunday() as usize]
+    }
+}
+
+impl From<Weekday> for chrono::Weekday {
+    fn from(weekday: Weekday) -> chrono::Weekday {
+        match weekday {
+            Weekday::Sunday => chrono::Weekday::Sun,
+            Weekday::Monday => chrono::Weekday::Mon,
+            Weekday::Tuesday => chrono::Weekday::Tue,
+            Weekday::Wednesday => chrono::Weekday::Wed,
+            Weekday::Thursday => chrono::Weekday::Thu,
+            Weekday::Friday => chrono::Weekday::Fri,
+            Weekday::Saturday => chrono::Weekday::Sat,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -30,21 +204,123 @@ pub(crate) fn chrono_from_fixed(t: f64) -> Option<DateTime<Utc>> {
 #[test]
 fn test_chrono_conversions() {
     let input = [
-        (730_120.5, Utc.ymd(2000, 1, 1).and_hms(12, 0, 0)),
-        (-61_387.0 + 256_f64.recip(), Utc.ymd(-168, 12, 5).and_hms_milli(0, 5, 37, 500)),
-        (-95_746_495.0, chrono::MIN_DATE.and_hms(0, 0, 0)),
-        (95_745_764.0, chrono::MAX_DATE.and_hms(0, 0, 0)),
+        (730_120.5, Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap()),
+        (
+            -61_387.0 + 256_f64.recip(),
+            Utc.with_ymd_and_hms(-168, 12, 5, 0, 5, 37).unwrap() + Duration::milliseconds(500),
+        ),
+        (-95_746_129.0, DateTime::<Utc>::MIN_UTC),
+        (95_745_399.0, Utc.from_utc_datetime(&NaiveDate::MAX.and_hms_opt(0, 0, 0).unwrap())),
     ];
     let iter = crate::data::TEST_DATA
         .iter()
         .copied()
-        .map(|(rd, (y, m, d), _, _, _, _)| (rd, Utc.ymd(y, m, d).and_hms(0, 0, 0)))
+        .map(|(rd, (y, m, d), _, _, _, _)| (rd, Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap()))
         .chain(input.iter().copied());
     for (rd, datetime) in iter {
         assert_eq!(rd, fixed_from_chrono(datetime));
         assert_eq!(chrono_from_fixed(rd).unwrap(), datetime);
     }
 
-    assert!(chrono_from_fixed(-95_746_496.0).is_none());
-    assert!(chrono_from_fixed(95_745_765.0).is_none());
+    assert!(chrono_from_fixed(-95_746_130.0).is_none());
+    assert!(chrono_from_fixed(95_745_400.0).is_none());
+}
+
+// A time zone for testing daylight saving time, at UTC+11 until 03:00 on April 5, 2020, UTC+10
+// until midnight on November 1, 2020, and UTC+11 after, like Lord Howe Island but with whole hours
+// and a transition at midnight
+#[cfg(test)]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TestZone;
+
+#[cfg(test)]
+impl TestZone {
+    fn offset_at(utc: &chrono::NaiveDateTime) -> chrono::FixedOffset {
+        let transition = |m, d, h| NaiveDate::from_ymd_opt(2020, m, d)?.and_hms_opt(h, 0, 0);
+        let dst = Some(*utc) < transition(4, 4, 16) || Some(*utc) >= transition(10, 31, 14);
+        chrono::FixedOffset::east_opt(if dst { 11 } else { 10 } * 3600).unwrap()
+    }
+}
+
+#[cfg(test)]
+impl TimeZone for TestZone {
+    type Offset = chrono::FixedOffset;
+
+    fn from_offset(_: &chrono::FixedOffset) -> TestZone {
+        TestZone
+    }
+
+    fn offset_from_local_date(
+        &self,
+        local: &NaiveDate,
+    ) -> chrono::LocalResult<chrono::FixedOffset> {
+        self.offset_from_local_datetime(&local.and_hms_opt(12, 0, 0).unwrap())
+    }
+
+    fn offset_from_local_datetime(
+        &self,
+        local: &chrono::NaiveDateTime,
+    ) -> chrono::LocalResult<chrono::FixedOffset> {
+        let mut offsets = [11, 10].iter().filter_map(|hours| {
+            let offset = chrono::FixedOffset::east_opt(hours * 3600).unwrap();
+            let utc = *local - Duration::hours(i64::from(*hours));
+            Some(offset).filter(|offset| *offset == TestZone::offset_at(&utc))
+        });
+        match (offsets.next(), offsets.next()) {
+            (Some(earliest), Some(latest)) => chrono::LocalResult::Ambiguous(earliest, latest),
+            (Some(offset), None) => chrono::LocalResult::Single(offset),
+            _ => chrono::LocalResult::None,
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> chrono::FixedOffset {
+        TestZone::offset_at(&utc.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &chrono::NaiveDateTime) -> chrono::FixedOffset {
+        TestZone::offset_at(utc)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+#[test]
+fn test_local_day_bounds() {
+    use chrono::FixedOffset;
+
+    // A day in UTC+10 begins at 14:00 UTC the day before
+    let t =
+        FixedOffset::east_opt(10 * 3600).unwrap().with_ymd_and_hms(2020, 11, 1, 9, 0, 0).unwrap();
+    let (start, end) = Day::bounds(&t);
+    assert_eq!(Day::fixed_date(&t), FixedDate::from_gregorian(2020, 11, 1).unwrap());
+    assert_eq!(start.rd(), f64::from(fixed_from_gregorian(2020, 10, 31)) + 14.0 / 24.0);
+    assert_eq!(end.rd(), start.rd() + 1.0);
+
+    // Midnight is skipped when daylight saving time begins, so the day begins at 01:00, and days
+    // are 23 or 25 hours long when daylight saving time begins or ends
+    let hours = |(start, end): (Moment, Moment)| ((end.rd() - start.rd()) * 24.0).round();
+    let t = TestZone.with_ymd_and_hms(2020, 11, 1, 7, 0, 0).unwrap();
+    assert_eq!(Day::fixed_date(&t), FixedDate::from_gregorian(2020, 11, 1).unwrap());
+    assert_eq!(Day::bounds(&t).0.rd(), f64::from(fixed_from_gregorian(2020, 10, 31)) + 14.0 / 24.0);
+    assert_eq!(hours(Day::bounds(&t)), 23.0);
+    let t = TestZone.with_ymd_and_hms(2020, 4, 5, 12, 0, 0).unwrap();
+    assert_eq!(hours(Day::bounds(&t)), 25.0);
+    let t = TestZone.with_ymd_and_hms(2020, 4, 6, 12, 0, 0).unwrap();
+    assert_eq!(hours(Day::bounds(&t)), 24.0);
+}
+
+#[cfg(all(test, feature = "compat-date"))]
+#[allow(deprecated)]
+#[test]
+fn test_compat_date() {
+    use crate::iter::daily_lunar_phase_iter;
+
+    let start = Utc.ymd(2020, 10, 1);
+    let end = Utc.ymd(2020, 11, 1);
+    let dates =
+        daily_lunar_phase_iter(start..end).map(|(_, date)| date).collect::<Vec<Date<Utc>>>();
+    let expected = daily_lunar_phase_iter::<NaiveDate, _>(start.naive_utc()..end.naive_utc())
+        .map(|(_, date)| Date::<Utc>::from_utc(date, Utc))
+        .collect::<Vec<_>>();
+    assert_eq!(dates, expected);
 }
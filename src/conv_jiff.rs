@@ -0,0 +1,209 @@
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+
+use crate::calendar::fixed_from_gregorian;
+use crate::moment::{
+    moment_from_parts, parts_from_moment, CivilDate, Day, FixedDate, LocalDate, Moment, Time,
+    Timestamp, Weekday, WEEKDAYS,
+};
+use crate::util::nonneg;
+use core::convert::TryFrom;
+use jiff::civil::Date;
+use jiff::tz::{Offset, TimeZone};
+use jiff::Zoned;
+
+fn fixed_from_date(date: Date) -> i32 {
+    let part = |n: i8| nonneg(n.into());
+    fixed_from_gregorian(date.year().into(), part(date.month()), part(date.day()))
+}
+
+fn date_from_fixed(date: i32) -> Option<Date> {
+    let (year, month, day) = FixedDate::from_rd(date).to_gregorian();
+    let part = |n: u32| i8::try_from(n).ok();
+    Date::new(i16::try_from(year).ok()?, part(month)?, part(day)?).ok()
+}
+
+fn moment_from_timestamp(t: jiff::Timestamp) -> Moment {
+    let dt = Offset::UTC.to_datetime(t);
+    let part = |n: i8| nonneg(n.into());
+    let date = (dt.year().into(), part(dt.month()), part(dt.day()));
+    let time =
+        (part(dt.hour()), part(dt.minute()), part(dt.second()), nonneg(dt.subsec_nanosecond()));
+    moment_from_parts(date, time)
+}
+
+fn timestamp_from_moment(moment: Moment) -> Option<jiff::Timestamp> {
+    let ((year, month, day), (hour, min, sec, nano)) = parts_from_moment(moment);
+    let part = |n: u32| i8::try_from(n).ok();
+    let date = Date::new(i16::try_from(year).ok()?, part(month)?, part(day)?).ok()?;
+    let dt = date.at(part(hour)?, part(min)?, part(sec)?, i32::try_from(nano).ok()?);
+    Offset::UTC.to_timestamp(dt).ok()
+}
+
+impl Time for jiff::Timestamp {
+    fn to_moment(&self) -> Moment {
+        moment_from_timestamp(*self)
+    }
+
+    fn with_moment(&self, moment: Moment) -> Option<jiff::Timestamp> {
+        timestamp_from_moment(moment)
+    }
+
+    fn fixed_date(&self) -> FixedDate {
+        FixedDate::from_rd(fixed_from_date(Offset::UTC.to_datetime(*self).date()))
+    }
+}
+
+impl Timestamp for jiff::Timestamp {
+    fn from_moment(moment: Moment) -> Option<jiff::Timestamp> {
+        timestamp_from_moment(moment)
+    }
+
+    fn min_value() -> jiff::Timestamp {
+        jiff::Timestamp::MIN
+    }
+
+    fn max_value() -> jiff::Timestamp {
+        jiff::Timestamp::MAX
+    }
+}
+
+impl Time for Zoned {
+    fn to_moment(&self) -> Moment {
+        moment_from_timestamp(self.timestamp())
+    }
+
+    fn with_moment(&self, moment: Moment) -> Option<Zoned> {
+        Some(timestamp_from_moment(moment)?.to_zoned(self.time_zone().clone()))
+    }
+
+    fn fixed_date(&self) -> FixedDate {
+        FixedDate::from_rd(fixed_from_date(self.date()))
+    }
+}
+
+/// A `Zoned` is also the day it falls on in its time zone, from local midnight to local midnight.
+/// The day containing a moment is represented by that moment in the same time zone.
+impl Day for Zoned {
+    type Time = jiff::Timestamp;
+
+    fn fixed_date(&self) -> FixedDate {
+        Time::fixed_date(self)
+    }
+
+    fn bounds(&self) -> (Moment, Moment) {
+        self.date().local_bounds(self.time_zone())
+    }
+
+    fn day_containing(&self, moment: Moment) -> Option<Zoned> {
+        Time::with_moment(self, moment)
+    }
+}
+
+impl Day for Date {
+    type Time = jiff::Timestamp;
+
+    fn fixed_date(&self) -> FixedDate {
+        FixedDate::from_rd(fixed_from_date(*self))
+    }
+
+    fn bounds(&self) -> (Moment, Moment) {
+        Day::bounds(&Day::fixed_date(self))
+    }
+
+    fn day_containing(&self, moment: Moment) -> Option<Date> {
+        date_from_fixed(moment.fixed_date().rd())
+    }
+}
+
+impl CivilDate for Date {
+    fn from_fixed(date: FixedDate) -> Option<Date> {
+        date_from_fixed(date.rd())
+    }
+
+    fn min_value() -> Date {
+        Date::MIN
+    }
+
+    fn max_value() -> Date {
+        Date::MAX
+    }
+}
+
+/// A civil `Date` in a `TimeZone` runs from local midnight to local midnight. When midnight is
+/// skipped by a daylight saving time transition, the day begins at the end of the gap.
+impl LocalDate<TimeZone> for Date {
+    type Local = Zoned;
+
+    fn local_bounds(&self, zone: &TimeZone) -> (Moment, Moment) {
+        let start_of_day = |date: Date| Some(date.to_zoned(zone.clone()).ok()?.to_moment());
+        let start = start_of_day(*self).unwrap_or_else(|| Day::fixed_date(self).moment());
+        // The last day Jiff can represent is assumed to be 24 hours long
+        let end = self.tomorrow().ok().and_then(start_of_day);
+        (start, end.unwrap_or_else(|| Moment::from_rd(start.rd() + 1.0)))
+    }
+
+    fn local_time(zone: &TimeZone, moment: Moment) -> Option<Zoned> {
+        Some(timestamp_from_moment(moment)?.to_zoned(zone.clone()))
+    }
+}
+
+impl From<jiff::civil::Weekday> for Weekday {
+    fn from(weekday: jiff::civil::Weekday) -> Weekday {
+        WEEKDAYS[nonneg(weekday.to_sunday_zero_offset().into()) as usize]
+    }
+}
+
+impl From<Weekday> for jiff::civil::Weekday {
+    fn from(weekday: Weekday) -> jiff::civil::Weekday {
+        match weekday {
+            Weekday::Sunday => jiff::civil::Weekday::Sunday,
+            Weekday::Monday => jiff::civil::Weekday::Monday,
+            Weekday::Tuesday => jiff::civil::Weekday::Tuesday,
+            Weekday::Wednesday => jiff::civil::Weekday::Wednesday,
+            Weekday::Thursday => jiff::civil::Weekday::Thursday,
+            Weekday::Friday => jiff::civil::Weekday::Friday,
+            Weekday::Saturday => jiff::civil::Weekday::Saturday,
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_jiff_conversions() {
+    use crate::phase::{Phase, PrincipalPhase};
+    use jiff::civil::date;
+
+    assert_eq!(
+        Day::fixed_date(&date(2020, 10, 31)),
+        FixedDate::from_gregorian(2020, 10, 31).unwrap()
+    );
+    assert_eq!(Weekday::from(date(2020, 10, 31).weekday()), Weekday::Saturday);
+    let t = jiff::Timestamp::new(1_604_155_739, 300_000_000).unwrap();
+    assert!((t.to_moment().rd() - Moment::from_unix_timestamp(1_604_155_739.3).rd()).abs() < 1e-9);
+    assert_eq!(
+        jiff::Timestamp::from_moment(t.to_moment()).unwrap().as_millisecond(),
+        t.as_millisecond()
+    );
+
+    // Lord Howe Island moves its clocks forward half an hour at 02:00 on the first Sunday in
+    // October, so October 4, 2020 is 23.5 hours long
+    let tz = TimeZone::posix("<+1030>-10:30<+11>-11,M10.1.0,M4.1.0").unwrap();
+    let hours = |(start, end): (Moment, Moment)| (end.rd() - start.rd()) * 24.0;
+    assert!((hours(date(2020, 10, 4).local_bounds(&tz)) - 23.5).abs() < 1e-6);
+    assert!((hours(date(2020, 10, 5).local_bounds(&tz)) - 24.0).abs() < 1e-6);
+
+    // The full moon at 14:49 UTC on October 31 is at 01:49 on November 1 at UTC+11
+    let phases = crate::daily_lunar_phase_iter_in(date(2020, 10, 1)..date(2020, 12, 1), tz.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(phases[4].0, PrincipalPhase::FullMoon);
+    assert_eq!(phases[4].1.date(), date(2020, 11, 1));
+    for (phase, t) in &phases {
+        assert_eq!(t.time_zone(), &tz);
+        assert_eq!(crate::daily_lunar_phase(t.clone()), Phase::from(*phase));
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+
+use crate::moment::{
+    moment_from_parts, parts_from_moment, CivilDate, Day, FixedDate, LocalDate, Moment, Time,
+    Timestamp, Weekday, WEEKDAYS,
+};
+use core::convert::TryFrom;
+use time::{Date, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+// The Julian Day number of R.D. 0
+const JD_OFFSET: i32 = 1_721_425;
+
+fn fixed_from_date(date: Date) -> i32 {
+    date.to_julian_day() - JD_OFFSET
+}
+
+fn date_from_fixed(date: i32) -> Option<Date> {
+    Date::from_julian_day(date.checked_add(JD_OFFSET)?).ok()
+}
+
+// The fraction of a day by which `offset` is ahead of UTC
+fn offset_days(offset: UtcOffset) -> f64 {
+    f64::from(offset.whole_seconds()) / 86400.0
+}
+
+fn time_from_moment(moment: Moment, offset: UtcOffset) -> Option<OffsetDateTime> {
+    let local = Moment::from_rd(moment.rd() + offset_days(offset));
+    let (_, (hour, min, sec, nano)) = parts_from_moment(local);
+    let part = |n| u8::try_from(n).ok();
+    let date = date_from_fixed(local.fixed_date().rd())?;
+    let datetime = date.with_hms_nano(part(hour)?, part(min)?, part(sec)?, nano).ok()?;
+    Some(datetime.assume_offset(offset))
+}
+
+impl Time for OffsetDateTime {
+    fn to_moment(&self) -> Moment {
+        let (year, month, day) = self.to_calendar_date();
+        let (hour, min, sec, nano) = self.to_hms_nano();
+        let date = (year, u32::from(u8::from(month)), u32::from(day));
+        let local = moment_from_parts(date, (hour.into(), min.into(), sec.into(), nano));
+        Moment::from_rd(local.rd() - offset_days(self.offset()))
+    }
+
+    fn with_moment(&self, moment: Moment) -> Option<OffsetDateTime> {
+        time_from_moment(moment, self.offset())
+    }
+
+    fn fixed_date(&self) -> FixedDate {
+        FixedDate::from_rd(fixed_from_date(self.date()))
+    }
+}
+
+impl Timestamp for OffsetDateTime {
+    fn from_moment(moment: Moment) -> Option<OffsetDateTime> {
+        time_from_moment(moment, UtcOffset::UTC)
+    }
+
+    fn min_value() -> OffsetDateTime {
+        PrimitiveDateTime::MIN.assume_utc()
+    }
+
+    fn max_value() -> OffsetDateTime {
+        PrimitiveDateTime::MAX.assume_utc()
+    }
+}
+
+/// An `OffsetDateTime` is also the day it falls on at its offset. The day containing a moment is
+/// represented by that moment at the same offset.
+impl Day for OffsetDateTime {
+    type Time = OffsetDateTime;
+
+    fn fixed_date(&self) -> FixedDate {
+        Time::fixed_date(self)
+    }
+
+    fn bounds(&self) -> (Moment, Moment) {
+        self.date().local_bounds(&self.offset())
+    }
+
+    fn day_containing(&self, moment: Moment) -> Option<OffsetDateTime> {
+        Time::with_moment(self, moment)
+    }
+}
+
+impl Day for Date {
+    type Time = OffsetDateTime;
+
+    fn fixed_date(&self) -> FixedDate {
+        FixedDate::from_rd(fixed_from_date(*self))
+    }
+
+    fn bounds(&self) -> (Moment, Moment) {
+        Day::bounds(&Day::fixed_date(self))
+    }
+
+    fn day_containing(&self, moment: Moment) -> Option<Date> {
+        date_from_fixed(moment.fixed_date().rd())
+    }
+}
+
+impl CivilDate for Date {
+    fn from_fixed(date: FixedDate) -> Option<Date> {
+        date_from_fixed(date.rd())
+    }
+
+    fn min_value() -> Date {
+        Date::MIN
+    }
+
+    fn max_value() -> Date {
+        Date::MAX
+    }
+}
+
+/// A `Date` at a `UtcOffset` runs from midnight to midnight at that offset.
+impl LocalDate<UtcOffset> for Date {
+    type Local = OffsetDateTime;
+
+    fn local_bounds(&self, zone: &UtcOffset) -> (Moment, Moment) {
+        let start = f64::from(fixed_from_date(*self)) - offset_days(*zone);
+        (Moment::from_rd(start), Moment::from_rd(start + 1.0))
+    }
+
+    fn local_time(zone: &UtcOffset, moment: Moment) -> Option<OffsetDateTime> {
+        time_from_moment(moment, *zone)
+    }
+}
+
+impl From<time::Weekday> for Weekday {
+    fn from(weekday: time::Weekday) -> Weekday {
+        WEEKDAYS[weekday.number_days_from_sunday() as usize]
+    }
+}
+
+impl From<Weekday> for time::Weekday {
+    fn from(weekday: Weekday) -> time::Weekday {
+        match weekday {
+            Weekday::Sunday => time::Weekday::Sunday,
+            Weekday::Monday => time::Weekday::Monday,
+            Weekday::Tuesday => time::Weekday::Tuesday,
+            Weekday::Wednesday => time::Weekday::Wednesday,
+            Weekday::Thursday => time::Weekday::Thursday,
+            Weekday::Friday => time::Weekday::Friday,
+            Weekday::Saturday => time::Weekday::Saturday,
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_time_conversions() {
+    use crate::phase::PrincipalPhase;
+    use time::Month;
+
+    let date = |m, d| Date::from_calendar_date(2020, Month::try_from(m).unwrap(), d).unwrap();
+    assert_eq!(Day::fixed_date(&date(10, 31)), FixedDate::from_gregorian(2020, 10, 31).unwrap());
+    assert_eq!(
+        Date::from_fixed(FixedDate::from_gregorian(2020, 10, 31).unwrap()),
+        Some(date(10, 31))
+    );
+    assert_eq!(Weekday::from(date(10, 31).weekday()), Weekday::Saturday);
+
+    // The full moon at 14:49 UTC on October 31 falls on November 1 at UTC+10
+    let offset = UtcOffset::from_hms(10, 0, 0).unwrap();
+    let start = date(10, 1).midnight().assume_offset(offset);
+    let end = date(11, 2).midnight().assume_offset(offset);
+    let (phase, t) = crate::lunar_phase_iter(start..end).last().unwrap();
+    assert_eq!(phase, PrincipalPhase::FullMoon);
+    assert!((t.to_moment().rd() - Moment::from_unix_timestamp(1_604_155_739.3).rd()).abs() < 1e-5);
+    assert_eq!(time_from_moment(t.to_moment(), offset).unwrap().date(), date(11, 1));
+
+    let mut iter = crate::daily_lunar_phase_iter_in(date(10, 25)..date(11, 5), offset);
+    let (phase, t) = iter.next().unwrap();
+    assert_eq!(phase, PrincipalPhase::FullMoon);
+    assert_eq!((t.date(), t.offset()), (date(11, 1), offset));
+    assert!(iter.next().is_none());
+}
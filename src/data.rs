@@ -178,6 +178,122 @@ pub(crate) const LUNAR_LONGITUDE_CORRECTION_TABLE: [(f64, f64, i32, f64, f64); 5
     (294.0, 2.0, 0, 3.0, 0.0),
 ];
 
+// Table 14.6, "Values of the arguments `$\tilde{v}$`, `$\tilde{w}$`, `$\tilde{x}$`, `$\tilde{y}$`,
+// and `$\tilde{z}$`, in `lunar-latitude`"
+pub(crate) const LUNAR_LATITUDE_CORRECTION_TABLE: [(f64, f64, i32, f64, f64); 60] = [
+    (5128122.0, 0.0, 0, 0.0, 1.0),
+    (280602.0, 0.0, 0, 1.0, 1.0),
+    (277693.0, 0.0, 0, 1.0, -1.0),
+    (173237.0, 2.0, 0, 0.0, -1.0),
+    (55413.0, 2.0, 0, -1.0, 1.0),
+    (46271.0, 2.0, 0, -1.0, -1.0),
+    (32573.0, 2.0, 0, 0.0, 1.0),
+    (17198.0, 0.0, 0, 2.0, 1.0),
+    (9266.0, 2.0, 0, 1.0, -1.0),
+    (8822.0, 0.0, 0, 2.0, -1.0),
+    (8216.0, 2.0, -1, 0.0, -1.0),
+    (4324.0, 2.0, 0, -2.0, -1.0),
+    (4200.0, 2.0, 0, 1.0, 1.0),
+    (-3359.0, 2.0, 1, 0.0, -1.0),
+    (2463.0, 2.0, -1, -1.0, 1.0),
+    (2211.0, 2.0, -1, 0.0, 1.0),
+    (2065.0, 2.0, -1, -1.0, -1.0),
+    (-1870.0, 0.0, 1, -1.0, -1.0),
+    (1828.0, 4.0, 0, -1.0, -1.0),
+    (-1794.0, 0.0, 1, 0.0, 1.0),
+    (-1749.0, 0.0, 0, 0.0, 3.0),
+    (-1565.0, 0.0, 1, -1.0, 1.0),
+    (-1491.0, 1.0, 0, 0.0, 1.0),
+    (-1475.0, 0.0, 1, 1.0, 1.0),
+    (-1410.0, 0.0, 1, 1.0, -1.0),
+    (-1344.0, 0.0, 1, 0.0, -1.0),
+    (-1335.0, 1.0, 0, 0.0, -1.0),
+    (1107.0, 0.0, 0, 3.0, 1.0),
+    (1021.0, 4.0, 0, 0.0, -1.0),
+    (833.0, 4.0, 0, -1.0, 1.0),
+    (777.0, 0.0, 0, 1.0, -3.0),
+    (671.0, 4.0, 0, -2.0, 1.0),
+    (607.0, 2.0, 0, 0.0, -3.0),
+    (596.0, 2.0, 0, 2.0, -1.0),
+    (491.0, 2.0, -1, 1.0, -1.0),
+    (-451.0, 2.0, 0, -2.0, 1.0),
+    (439.0, 0.0, 0, 3.0, -1.0),
+    (422.0, 2.0, 0, 2.0, 1.0),
+    (421.0, 2.0, 0, -3.0, -1.0),
+    (-366.0, 2.0, 1, -1.0, 1.0),
+    (-351.0, 2.0, 1, 0.0, 1.0),
+    (331.0, 4.0, 0, 0.0, 1.0),
+    (315.0, 2.0, -1, 1.0, 1.0),
+    (302.0, 2.0, -2, 0.0, -1.0),
+    (-283.0, 0.0, 0, 1.0, 3.0),
+    (-229.0, 2.0, 1, 1.0, -1.0),
+    (223.0, 1.0, 1, 0.0, -1.0),
+    (223.0, 1.0, 1, 0.0, 1.0),
+    (-220.0, 0.0, 1, -2.0, -1.0),
+    (-220.0, 2.0, 1, -1.0, -1.0),
+    (-185.0, 1.0, 0, 1.0, 1.0),
+    (181.0, 2.0, -1, -2.0, -1.0),
+    (-177.0, 0.0, 1, 2.0, 1.0),
+    (176.0, 4.0, 0, -2.0, -1.0),
+    (166.0, 4.0, -1, -1.0, -1.0),
+    (-164.0, 1.0, 0, 1.0, -1.0),
+    (132.0, 4.0, 0, 1.0, -1.0),
+    (-119.0, 1.0, 0, -1.0, -1.0),
+    (115.0, 4.0, -1, 0.0, -1.0),
+    (107.0, 2.0, -2, 0.0, 1.0),
+];
+
+// Table 14.7, "Values of the arguments `$\tilde{v}$`, `$\tilde{w}$`, `$\tilde{x}$`, `$\tilde{y}$`,
+// and `$\tilde{z}$`, in `lunar-distance`"
+pub(crate) const LUNAR_DISTANCE_CORRECTION_TABLE: [(f64, f64, i32, f64, f64); 46] = [
+    (-20905355.0, 0.0, 0, 1.0, 0.0),
+    (-3699111.0, 2.0, 0, -1.0, 0.0),
+    (-2955968.0, 2.0, 0, 0.0, 0.0),
+    (-569925.0, 0.0, 0, 2.0, 0.0),
+    (48888.0, 0.0, 1, 0.0, 0.0),
+    (-3149.0, 0.0, 0, 0.0, 2.0),
+    (246158.0, 2.0, 0, -2.0, 0.0),
+    (-152138.0, 2.0, -1, -1.0, 0.0),
+    (-170733.0, 2.0, 0, 1.0, 0.0),
+    (-204586.0, 2.0, -1, 0.0, 0.0),
+    (-129620.0, 0.0, 1, -1.0, 0.0),
+    (108743.0, 1.0, 0, 0.0, 0.0),
+    (104755.0, 0.0, 1, 1.0, 0.0),
+    (10321.0, 2.0, 0, 0.0, -2.0),
+    (79661.0, 0.0, 0, 1.0, -2.0),
+    (-34782.0, 4.0, 0, -1.0, 0.0),
+    (-23210.0, 0.0, 0, 3.0, 0.0),
+    (-21636.0, 4.0, 0, -2.0, 0.0),
+    (24208.0, 2.0, 1, -1.0, 0.0),
+    (30824.0, 2.0, 1, 0.0, 0.0),
+    (-8379.0, 1.0, 0, -1.0, 0.0),
+    (-16675.0, 1.0, 1, 0.0, 0.0),
+    (-12831.0, 2.0, -1, 1.0, 0.0),
+    (-10445.0, 2.0, 0, 2.0, 0.0),
+    (-11650.0, 4.0, 0, 0.0, 0.0),
+    (14403.0, 2.0, 0, -3.0, 0.0),
+    (-7003.0, 0.0, 1, -2.0, 0.0),
+    (10056.0, 2.0, -1, -2.0, 0.0),
+    (6322.0, 1.0, 0, 1.0, 0.0),
+    (-9884.0, 2.0, -2, 0.0, 0.0),
+    (5751.0, 0.0, 1, 2.0, 0.0),
+    (-4950.0, 2.0, -2, -1.0, 0.0),
+    (4130.0, 2.0, 0, 1.0, -2.0),
+    (-3958.0, 4.0, -1, -1.0, 0.0),
+    (3258.0, 3.0, 0, -1.0, 0.0),
+    (2616.0, 2.0, 1, 1.0, 0.0),
+    (-1897.0, 4.0, -1, -2.0, 0.0),
+    (-2117.0, 0.0, 2, -1.0, 0.0),
+    (2354.0, 2.0, 2, -1.0, 0.0),
+    (-1423.0, 4.0, 0, 1.0, 0.0),
+    (-1117.0, 0.0, 0, 4.0, 0.0),
+    (-1571.0, 4.0, -1, 0.0, 0.0),
+    (-1739.0, 1.0, 0, -2.0, 0.0),
+    (-4421.0, 0.0, 0, 2.0, -2.0),
+    (1165.0, 0.0, 2, 1.0, 0.0),
+    (8752.0, 2.0, 0, -1.0, -2.0),
+];
+
 // Subset of Appendix C: Sample Data
 //
 // |      |     Gregorian      | Ephemeris  | Solar longitude |   Lunar   | New moon at |
@@ -219,3 +335,43 @@ pub(crate) const TEST_DATA: [(f64, (i32, u32, u32), f64, f64, f64, f64); 33] = [
     (744313.0, (2038, 11, 10), 0.000963, 228.184879, 26.68206, 744329.573999),
     (764652.0, (2094, 7, 18), 0.002913, 116.439352, 175.500822, 764676.191273),
 ];
+
+// The exact results of `lunar_phase` and `lunar_phase_at_or_after(90.0, _)` at each moment of
+// `TEST_DATA` with the `deterministic` feature, as the bits of each `f64`, which must be identical
+// on every target
+#[cfg(all(test, feature = "deterministic"))]
+pub(crate) const DETERMINISTIC_DATA: [(f64, u64, u64); 33] = [
+    (-214193.0, 0x405f7758c03544f8, 0xc10a24b36e1f10ca),
+    (-61387.0, 0x4073b1e45e272fce, 0xc0edf7d9405eeeb2),
+    (25469.0, 0x404067b818e4eaf4, 0x40d8e08f4b0274e3),
+    (49217.0, 0x4059f8877ac70cac, 0x40e80baf59986cea),
+    (171307.0, 0x406c878498506524, 0x4104e9f2e0615960),
+    (210155.0, 0x4048b4e2c2bc59f4, 0x4109a77068e44c24),
+    (253427.0, 0x4065718fffe06bae, 0x410ef04c5135e515),
+    (369740.0, 0x4050259e32b443c9, 0x411691393504a184),
+    (400085.0, 0x4070e3f8672f1f49, 0x41186b8aaf36d8b9),
+    (434355.0, 0x405692d33ff9d22d, 0x411a8342e7de4fff),
+    (452605.0, 0x405609b92931b684, 0x411b9ff494742f64),
+    (470160.0, 0x40705f02c4f1a286, 0x411cb27e11d2ec52),
+    (473837.0, 0x4056dc4b341ca9e8, 0x411cec2a6a586d0c),
+    (507850.0, 0x4021ef40a31087b0, 0x411eff406ac9625a),
+    (524156.0, 0x4051d02bd7d53fcc, 0x411ffdf6d4e42d42),
+    (544676.0, 0x404102979c69a349, 0x41209f51df84070f),
+    (567118.0, 0x4028a8bef9f664f0, 0x41214ea82ddb033e),
+    (569477.0, 0x4074fbfa1dc86e8d, 0x4121611eb916f159),
+    (601716.0, 0x406d51c112cd3504, 0x41225d0bd5a17b07),
+    (613424.0, 0x4047530119f08c40, 0x4122b8678498f40e),
+    (626596.0, 0x404ffc92014c8d98, 0x41231f4c919393bc),
+    (645554.0, 0x404b0ca1de26be94, 0x4123b36a57dfbec6),
+    (664224.0, 0x405f3ba69e25eedc, 0x412445754a3548ad),
+    (671401.0, 0x40623fde2a34a757, 0x41247d83688afb13),
+    (694799.0, 0x40704395aa591c70, 0x4125343c9e7d5258),
+    (704424.0, 0x406d4a827fa34906, 0x41257f71e9950e3a),
+    (708842.0, 0x405a355a301695c4, 0x4125a20c6bfdba63),
+    (709409.0, 0x40643b10618d75d8, 0x4125a670d2a06324),
+    (709580.0, 0x405704effcc52e40, 0x4125a7d247df99fa),
+    (727274.0, 0x406375eccad51a84, 0x41263204d76a1d55),
+    (728714.0, 0x4052ffa7cd12aa3c, 0x41263d167d4388e8),
+    (744313.0, 0x4063dffa77e4d0f5, 0x4126b721105cb66a),
+    (764652.0, 0x404dc4f0a901dc2c, 0x412755dd7352f36f),
+];
@@ -0,0 +1,288 @@
+// Copyright (c) 2018 Edward M. Reingold and Nachum Dershowitz
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+
+#![allow(clippy::unreadable_literal)]
+
+use crate::calendar::{fixed_from_gregorian, gregorian_year_from_fixed};
+use crate::moment::Moment;
+use crate::util::{polynomial, powi};
+#[cfg(feature = "alloc")]
+use {
+    crate::moment::{FixedDate, MAX_YEAR, MIN_YEAR},
+    crate::util::{floor, i32},
+    alloc::vec::Vec,
+    core::iter::FromIterator,
+};
+
+/// A model of ΔT, the difference between Terrestrial Time and Universal Time.
+///
+/// The positions of the Sun and Moon are calculated in Terrestrial Time, a uniform time scale,
+/// while moments in this crate are in Universal Time, which follows the irregular rotation of the
+/// Earth. ΔT can only be measured for the past, so every model is an estimate outside of the
+/// period it was fitted to.
+///
+/// Most functions in this crate use [`EspenakMeeus`]. To use a different model, see
+/// [`Ephemeris`](crate::Ephemeris).
+pub trait DeltaT {
+    /// Returns ΔT in seconds at moment `t`.
+    fn delta_t(&self, t: Moment) -> f64;
+}
+
+impl<M: DeltaT + ?Sized> DeltaT for &M {
+    fn delta_t(&self, t: Moment) -> f64 {
+        (**self).delta_t(t)
+    }
+}
+
+/// The polynomial expressions for ΔT by Espenak and Meeus, as used by <i>Calendrical
+/// Calculations</i>.
+///
+/// The expressions are fitted to historical values between -500 and 2005 and to a prediction
+/// through 2150. Outside of that range, this model is the same as [`MorrisonStephenson2004`].
+///
+/// This is the model used by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct EspenakMeeus;
+
+impl DeltaT for EspenakMeeus {
+    fn delta_t(&self, t: Moment) -> f64 {
+        let year_i = gregorian_year_from_fixed(t.rd());
+        let year = f64::from(year_i);
+        match year_i {
+            2051..=2150 => {
+                -20.0 + 32.0 * powi((year - 1820.0) / 100.0, 2) + 0.5628 * (2150.0 - year)
+            }
+            2006..=2050 => polynomial(year - 2000.0, &[62.92, 0.32217, 0.005589]),
+            1987..=2005 => polynomial(
+                year - 2000.0,
+                &[63.86, 0.3345, -0.060374, 0.0017275, 0.000651814, 0.00002373599],
+            ),
+            1900..=1986 => {
+                polynomial(
+                    f64::from(
+                        fixed_from_gregorian(year_i, 7, 1) - fixed_from_gregorian(1900, 1, 1),
+                    ) / 36525.0,
+                    &[
+                        -0.00002, 0.000297, 0.025184, -0.181133, 0.553040, -0.861938, 0.677066,
+                        -0.212591,
+                    ],
+                ) * 86400.0
+            }
+            1800..=1899 => {
+                polynomial(
+                    f64::from(
+                        fixed_from_gregorian(year_i, 7, 1) - fixed_from_gregorian(1900, 1, 1),
+                    ) / 36525.0,
+                    &[
+                        -0.000009, 0.003844, 0.083563, 0.865736, 4.867575, 15.845535, 31.332267,
+                        38.291999, 28.316289, 11.636204, 2.043794,
+                    ],
+                ) * 86400.0
+            }
+            1700..=1799 => {
+                polynomial(year - 1700.0, &[8.118780842, -0.005092142, 0.003336121, -0.0000266484])
+            }
+            1600..=1699 => polynomial(year - 1600.0, &[120.0, -0.9808, -0.01532, 0.000140272128]),
+            500..=1599 => polynomial(
+                (year - 1000.0) / 100.0,
+                &[1574.2, -556.01, 71.23472, 0.319781, -0.8503463, -0.005050998, 0.0083572073],
+            ),
+            -499..=499 => polynomial(
+                year / 100.0,
+                &[10583.6, -1014.41, 33.78311, -5.952053, -0.1798452, 0.022174192, 0.0090316521],
+            ),
+            _ => MorrisonStephenson2004.delta_t(t),
+        }
+    }
+}
+
+/// The long-term parabola for ΔT by Morrison and Stephenson (2004), fitted to records of ancient
+/// eclipses.
+///
+/// This model changes smoothly with the year and extrapolates the average slowing of the Earth's
+/// rotation, which makes it suited to the distant past and future. It does not follow the decadal
+/// fluctuations of the last few centuries.
+///
+/// ```
+/// use esbat::{DeltaT, FixedDate, MorrisonStephenson2004};
+///
+/// let t = FixedDate::from_gregorian(-1000, 1, 1).unwrap().moment();
+/// assert!((MorrisonStephenson2004.delta_t(t) - 25428.0).abs() < 10.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct MorrisonStephenson2004;
+
+impl DeltaT for MorrisonStephenson2004 {
+    fn delta_t(&self, t: Moment) -> f64 {
+        let year = f64::from(gregorian_year_from_fixed(t.rd()));
+        polynomial((year - 1820.0) / 100.0, &[-20.0, 0.0, 32.0])
+    }
+}
+
+/// A constant ΔT, in seconds.
+///
+/// `FixedDeltaT(0.0)` treats Universal Time as Terrestrial Time.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FixedDeltaT(pub f64);
+
+impl DeltaT for FixedDeltaT {
+    fn delta_t(&self, _t: Moment) -> f64 {
+        self.0
+    }
+}
+
+/// A table of measured or predicted values of ΔT, such as those published by the IERS.
+///
+/// ΔT is interpolated linearly between entries. Before the first entry and after the last,
+/// [`EspenakMeeus`] is used instead.
+///
+/// Tables can be parsed from text with one entry per line, in either of two forms:
+///
+/// - a year, month, day and ΔT in seconds, as in the IERS `deltat.data` file
+/// - a decimal year and ΔT in seconds, optionally followed by its uncertainty, as in the IERS
+///   `historic_deltat.data` file
+///
+/// Blank lines and lines beginning with `#` are ignored.
+///
+/// ```
+/// use esbat::{DeltaT, DeltaTTable, FixedDate};
+///
+/// let table = DeltaTTable::parse(
+///     "# year month day delta-t
+///      2020  1  1  69.3612
+///      2020  2  1  69.3871",
+/// )
+/// .unwrap();
+/// let t = FixedDate::from_gregorian(2020, 1, 16).unwrap().moment();
+/// assert!((table.delta_t(t) - 69.374).abs() < 0.001);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeltaTTable {
+    // Moments (as R.D.) and ΔT in seconds, sorted by moment
+    entries: Vec<(f64, f64)>,
+}
+
+#[cfg(feature = "alloc")]
+impl DeltaTTable {
+    /// Parses a table from text. Returns `None` if any line is not a valid entry.
+    pub fn parse(text: &str) -> Option<DeltaTTable> {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(parse_entry)
+            .collect()
+    }
+
+    /// Reads and parses a table from a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, or an error with kind
+    /// [`InvalidData`](std::io::ErrorKind::InvalidData) if any line is not a valid entry.
+    #[cfg(feature = "std")]
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<DeltaTTable> {
+        let text = std::fs::read_to_string(path)?;
+        DeltaTTable::parse(&text).ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid delta T table entry")
+        })
+    }
+}
+
+// The moment and ΔT of a line of a table
+#[cfg(feature = "alloc")]
+fn parse_entry(line: &str) -> Option<(Moment, f64)> {
+    let fields = line.split_whitespace().collect::<Vec<_>>();
+    match *fields.as_slice() {
+        [year, month, day, value] => {
+            let date = FixedDate::from_gregorian(
+                year.parse().ok()?,
+                month.parse().ok()?,
+                day.parse().ok()?,
+            )?;
+            Some((date.moment(), value.parse().ok()?))
+        }
+        [year, value] | [year, value, _] => {
+            let year = year.parse::<f64>().ok()?;
+            if !(f64::from(MIN_YEAR)..f64::from(MAX_YEAR)).contains(&year) {
+                return None;
+            }
+            let whole = i32(floor(year));
+            let start = FixedDate::from_gregorian(whole, 1, 1)?.moment().rd();
+            let end = FixedDate::from_gregorian(whole + 1, 1, 1)?.moment().rd();
+            Some((
+                Moment::from_rd(start + (year - floor(year)) * (end - start)),
+                value.parse().ok()?,
+            ))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromIterator<(Moment, f64)> for DeltaTTable {
+    /// Collects a table from moments and the values of ΔT at them, in seconds.
+    fn from_iter<I: IntoIterator<Item = (Moment, f64)>>(iter: I) -> DeltaTTable {
+        let mut entries = iter.into_iter().map(|(t, value)| (t.rd(), value)).collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.0.total_cmp(&b.0));
+        DeltaTTable { entries }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl DeltaT for DeltaTTable {
+    fn delta_t(&self, t: Moment) -> f64 {
+        let x = t.rd();
+        let i = self.entries.partition_point(|(rd, _)| *rd <= x);
+        if i == 0 || (i == self.entries.len() && self.entries[i - 1].0 < x) {
+            return EspenakMeeus.delta_t(t);
+        }
+        let (x0, y0) = self.entries[i - 1];
+        match self.entries.get(i) {
+            Some(&(x1, y1)) => y0 + (y1 - y0) * (x - x0) / (x1 - x0),
+            None => y0,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+#[test]
+fn test_delta_t() {
+    let moment = |y, m, d| FixedDate::from_gregorian(y, m, d).unwrap().moment();
+
+    // Espenak and Meeus give ΔT of about 64.7 seconds in 2005, and the models agree in -1000
+    assert!((EspenakMeeus.delta_t(moment(2005, 1, 1)) - 64.7).abs() < 0.1);
+    assert!((MorrisonStephenson2004.delta_t(moment(1820, 6, 1)) - -20.0).abs() < f64::EPSILON);
+    assert!(
+        (EspenakMeeus.delta_t(moment(-1000, 1, 1))
+            - MorrisonStephenson2004.delta_t(moment(-1000, 1, 1)))
+        .abs()
+            < f64::EPSILON
+    );
+    assert!((FixedDeltaT(42.0).delta_t(moment(2000, 1, 1)) - 42.0).abs() < f64::EPSILON);
+
+    let table = DeltaTTable::parse(
+        "# historic
+         1900.0  -2.72
+         1900.5  -2.20
+
+         1901  1  1  -1.5",
+    )
+    .unwrap();
+    assert!((table.delta_t(moment(1900, 1, 1)) - -2.72).abs() < 1e-9);
+    assert!((table.delta_t(moment(1900, 10, 1)) - -1.85).abs() < 0.01);
+    assert!((table.delta_t(moment(1901, 1, 1)) - -1.5).abs() < 1e-9);
+    assert!(
+        (table.delta_t(moment(1950, 1, 1)) - EspenakMeeus.delta_t(moment(1950, 1, 1))).abs()
+            < f64::EPSILON
+    );
+    assert!(DeltaTTable::parse("1900 -2.72 0.1 0.1 0.1").is_none());
+    assert!(DeltaTTable::parse("1900 13 1 -2.72").is_none());
+    assert!(DeltaTTable::parse("5879999.5 -2.72").is_none());
+    assert!(DeltaTTable::parse("-2147483648 1 1 -2.72").is_none());
+}
@@ -0,0 +1,133 @@
+// Copyright (c) 2018 Edward M. Reingold and Nachum Dershowitz
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+//
+// Functions from chapters 9, "The Ecclesiastical Calendars", and 14, "Time and Astronomy", of:
+//
+//     E. M. Reingold and N. Dershowitz, Calendrical Calculations: The Ultimate Edition.
+//         Cambridge University Press, 2018. doi:10.1017/9781107415058
+//
+// See `calendar.rs` for the representation of dates and moments.
+
+use crate::calendar::{
+    fixed_from_gregorian, fixed_from_julian, kday_after, lunar_phase_at_or_after,
+    solar_longitude_after,
+};
+use crate::delta_t::{DeltaT, EspenakMeeus};
+use crate::moment::{CivilDate, FixedDate};
+use crate::util::{floor, i32};
+
+const SUNDAY: i32 = 0;
+
+// The longitude of Jerusalem, in degrees
+const JERUSALEM_LONGITUDE: f64 = 35.24;
+
+fn orthodox_easter(year: i32) -> i32 {
+    let shifted_epact = (14 + 11 * year.rem_euclid(19)).rem_euclid(30);
+    let julian_year = if year > 0 { year } else { year - 1 };
+    let paschal_moon = fixed_from_julian(julian_year, 4, 19) - shifted_epact;
+    kday_after(SUNDAY, paschal_moon)
+}
+
+fn gregorian_easter(year: i32) -> i32 {
+    let century = year.div_euclid(100) + 1;
+    let shifted_epact = (14 + 11 * year.rem_euclid(19) - (3 * century).div_euclid(4)
+        + (5 + 8 * century).div_euclid(25))
+    .rem_euclid(30);
+    let adjusted_epact = if shifted_epact == 0 || (shifted_epact == 1 && 10 < year.rem_euclid(19)) {
+        shifted_epact + 1
+    } else {
+        shifted_epact
+    };
+    let paschal_moon = fixed_from_gregorian(year, 4, 19) - adjusted_epact;
+    kday_after(SUNDAY, paschal_moon)
+}
+
+fn astronomical_easter(year: i32, dt: &dyn DeltaT) -> i32 {
+    let equinox = solar_longitude_after(0.0, f64::from(fixed_from_gregorian(year, 1, 1)), dt);
+    let full_moon = lunar_phase_at_or_after(180.0, equinox, dt);
+    let paschal_moon = i32(floor(full_moon + JERUSALEM_LONGITUDE / 360.0));
+    kday_after(SUNDAY, paschal_moon)
+}
+
+// =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=
+
+/// A method of determining the date of Easter (a <i>computus</i>).
+///
+/// Easter is the first Sunday after the paschal full moon, the first full moon on or after the
+/// vernal equinox. The ecclesiastical methods approximate the full moon and fix the equinox at 21
+/// March, so they occasionally disagree with the astronomical method by a week or a month. For the
+/// date of Passover, see [`HebrewHoliday::Passover`](crate::HebrewHoliday::Passover).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Computus {
+    /// The Gregorian ecclesiastical tables, used by Western churches.
+    Gregorian,
+    /// The Julian ecclesiastical tables, used by Eastern Orthodox churches. The date is converted
+    /// to the Gregorian calendar.
+    Orthodox,
+    /// The first Sunday after the first true full moon after the true vernal equinox, as seen at
+    /// the meridian of Jerusalem. This is the method proposed by the World Council of Churches in
+    /// 1997.
+    Astronomical,
+}
+
+/// Returns the date of Easter Sunday in a year of the Gregorian calendar.
+///
+/// Returns `None` if the date cannot be represented by `D`.
+///
+/// ```
+/// use esbat::{Computus, FixedDate};
+///
+/// let date = |y, m, d| FixedDate::from_gregorian(y, m, d);
+/// assert_eq!(esbat::easter(2021, Computus::Gregorian), date(2021, 4, 4));
+/// assert_eq!(esbat::easter(2021, Computus::Orthodox), date(2021, 5, 2));
+///
+/// // The ecclesiastical full moon fell on 20 March, a day before the equinox of the tables, but the
+/// // true full moon fell the day after the true equinox.
+/// assert_eq!(esbat::easter(2019, Computus::Gregorian), date(2019, 4, 21));
+/// assert_eq!(esbat::easter(2019, Computus::Astronomical), date(2019, 3, 24));
+/// ```
+pub fn easter<D: CivilDate>(year: i32, computus: Computus) -> Option<D> {
+    easter_with(year, computus, &EspenakMeeus)
+}
+
+pub(crate) fn easter_with<D: CivilDate>(
+    year: i32,
+    computus: Computus,
+    dt: &dyn DeltaT,
+) -> Option<D> {
+    D::from_fixed(FixedDate::from_rd(match computus {
+        Computus::Gregorian => gregorian_easter(year),
+        Computus::Orthodox => orthodox_easter(year),
+        Computus::Astronomical => astronomical_easter(year, dt),
+    }))
+}
+
+#[cfg(test)]
+#[test]
+fn test_easter() {
+    let easters = [
+        (2018, (4, 1), (4, 8), (4, 1)),
+        (2019, (4, 21), (4, 28), (3, 24)),
+        (2020, (4, 12), (4, 19), (4, 12)),
+        (2021, (4, 4), (5, 2), (4, 4)),
+        (2022, (4, 17), (4, 24), (4, 17)),
+        (2023, (4, 9), (4, 16), (4, 9)),
+        (2024, (3, 31), (5, 5), (3, 31)),
+        (2025, (4, 20), (4, 20), (4, 20)),
+        (2038, (4, 25), (4, 25), (3, 28)),
+    ];
+    for (year, gregorian, orthodox, astronomical) in &easters {
+        for (computus, (month, day)) in &[
+            (Computus::Gregorian, gregorian),
+            (Computus::Orthodox, orthodox),
+            (Computus::Astronomical, astronomical),
+        ] {
+            assert_eq!(easter(*year, *computus), FixedDate::from_gregorian(*year, *month, *day));
+        }
+    }
+}
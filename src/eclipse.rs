@@ -0,0 +1,291 @@
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+//
+// Eclipse circumstances are from:
+//
+//     J. Meeus, Astronomical Algorithms, 2nd ed. Willmann-Bell, 1998. Chapter 54, "Eclipses".
+//
+// `k` is the lunation index of chapter 49: integers are new moons, integers plus one half are
+// full moons, and 0 is the new moon of January 6, 2000. It is the same index as `nth_new_moon`,
+// less 24724.
+
+#![allow(clippy::unreadable_literal)]
+
+use crate::calendar::{
+    lunar_distance, nth_new_moon, solar_distance, universal_from_dynamical, J2000,
+    MEAN_SYNODIC_MONTH,
+};
+use crate::delta_t::{DeltaT, EspenakMeeus};
+use crate::iter::{moment_bounds, Iter};
+use crate::moment::{Moment, Timestamp};
+use crate::phase::PrincipalPhase;
+use crate::util::{arcsin, cos, polynomial, rem_euclid, round, sin, sqrt};
+use core::iter::FusedIterator;
+use core::ops::RangeBounds;
+
+/// The kinds of lunar eclipse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LunarEclipseKind {
+    /// The Moon passes through the Earth's penumbra only.
+    Penumbral,
+    /// Part of the Moon passes through the Earth's umbra.
+    Partial,
+    /// The entire Moon passes through the Earth's umbra.
+    Total,
+}
+
+/// The kinds of solar eclipse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SolarEclipseKind {
+    /// Only the Moon's penumbra touches the Earth.
+    Partial,
+    /// The Moon's antumbra touches the Earth, leaving a ring of the Sun visible.
+    Annular,
+    /// The Moon's umbra touches the Earth, covering the Sun completely.
+    Total,
+    /// The eclipse is annular along part of its path and total along the rest.
+    Hybrid,
+}
+
+/// The kinds of eclipse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EclipseKind {
+    /// The Earth's shadow falls on the Moon, at a full moon.
+    Lunar(LunarEclipseKind),
+    /// The Moon's shadow falls on the Earth, at a new moon.
+    Solar(SolarEclipseKind),
+}
+
+/// A lunar or solar eclipse.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Eclipse<T = Moment> {
+    /// The kind of eclipse.
+    pub kind: EclipseKind,
+    /// The moment of greatest eclipse.
+    pub greatest: T,
+    /// The magnitude of the eclipse at greatest eclipse.
+    ///
+    /// For lunar eclipses, this is the fraction of the Moon's diameter immersed in the umbra
+    /// (or, for penumbral eclipses, the penumbra). For partial solar eclipses, this is the
+    /// fraction of the Sun's diameter covered by the Moon; for central solar eclipses, this is
+    /// the ratio of the apparent diameters of the Moon and the Sun.
+    pub magnitude: f64,
+}
+
+// Returns the moment of greatest eclipse, the kind of eclipse, and its magnitude, if there is an
+// eclipse at the syzygy `k`.
+#[allow(clippy::many_single_char_names, clippy::similar_names)]
+fn eclipse(k: f64, dt: &dyn DeltaT) -> Option<(f64, EclipseKind, f64)> {
+    let full = rem_euclid(k, 1.0) > 0.25;
+    let c = k / 1236.85;
+    let f =
+        polynomial(c, &[160.7108 + 390.67050284 * k, 0.0, -0.0016118, -0.00000227, 0.000000011]);
+    if sin(f).abs() > 0.36 {
+        return None;
+    }
+
+    let approx = J2000
+        + polynomial(
+            c,
+            &[5.09766 + MEAN_SYNODIC_MONTH * k, 0.0, 0.00015437, -0.000000150, 0.00000000073],
+        );
+    let e = polynomial(c, &[1.0, -0.002516, -0.0000074]);
+    let m = polynomial(c, &[2.5534 + 29.10535670 * k, 0.0, -0.0000014, -0.00000011]);
+    let m_prime =
+        polynomial(c, &[201.5643 + 385.81693528 * k, 0.0, 0.0107582, 0.00001238, -0.000000058]);
+    let omega = polynomial(c, &[124.7746 - 1.56375588 * k, 0.0, 0.0020672, 0.00000215]);
+    let f1 = f - 0.02665 * sin(omega);
+    let a1 = polynomial(c, &[299.77 + 0.107408 * k, 0.0, -0.009173]);
+
+    let correction = if full {
+        -0.4065 * sin(m_prime) + 0.1727 * e * sin(m)
+    } else {
+        -0.4075 * sin(m_prime) + 0.1721 * e * sin(m)
+    } + 0.0161 * sin(2.0 * m_prime)
+        - 0.0097 * sin(2.0 * f1)
+        + 0.0073 * e * sin(m_prime - m)
+        - 0.0050 * e * sin(m_prime + m)
+        - 0.0023 * sin(m_prime - 2.0 * f1)
+        + 0.0021 * e * sin(2.0 * m)
+        + 0.0012 * sin(m_prime + 2.0 * f1)
+        + 0.0006 * e * sin(2.0 * m_prime + m)
+        - 0.0004 * sin(3.0 * m_prime)
+        - 0.0003 * e * sin(m + 2.0 * f1)
+        + 0.0003 * sin(a1)
+        - 0.0002 * e * sin(m - 2.0 * f1)
+        - 0.0002 * e * sin(2.0 * m_prime - m)
+        - 0.0002 * sin(omega);
+    let greatest = universal_from_dynamical(approx + correction, dt);
+
+    let p = 0.2070 * e * sin(m) + 0.0024 * e * sin(2.0 * m) - 0.0392 * sin(m_prime)
+        + 0.0116 * sin(2.0 * m_prime)
+        - 0.0073 * e * sin(m_prime + m)
+        + 0.0067 * e * sin(m_prime - m)
+        + 0.0118 * sin(2.0 * f1);
+    let q = 5.2207 - 0.0048 * e * cos(m) + 0.0020 * e * cos(2.0 * m)
+        - 0.3299 * cos(m_prime)
+        - 0.0060 * e * cos(m_prime + m)
+        + 0.0041 * e * cos(m_prime - m);
+    let w = cos(f1).abs();
+    let gamma = ((p * cos(f1) + q * sin(f1)) * (1.0 - 0.0048 * w)).abs();
+    let u = 0.0059 + 0.0046 * e * cos(m) - 0.0182 * cos(m_prime) + 0.0004 * cos(2.0 * m_prime)
+        - 0.0005 * cos(m + m_prime);
+
+    if full {
+        let penumbral = (1.5573 + u - gamma) / 0.5450;
+        let umbral = (1.0128 - u - gamma) / 0.5450;
+        let (kind, magnitude) = if umbral >= 1.0 {
+            (LunarEclipseKind::Total, umbral)
+        } else if umbral > 0.0 {
+            (LunarEclipseKind::Partial, umbral)
+        } else if penumbral > 0.0 {
+            (LunarEclipseKind::Penumbral, penumbral)
+        } else {
+            return None;
+        };
+        Some((greatest, EclipseKind::Lunar(kind), magnitude))
+    } else {
+        if gamma > 1.5433 + u {
+            return None;
+        }
+        let kind = if gamma < 0.9972 {
+            if u < 0.0 {
+                SolarEclipseKind::Total
+            } else if u > 0.0047 || u >= 0.00464 * sqrt(1.0 - gamma * gamma) {
+                SolarEclipseKind::Annular
+            } else {
+                SolarEclipseKind::Hybrid
+            }
+        } else if gamma < 0.9972 + u.abs() {
+            // Non-central total or annular eclipses
+            if u < 0.0 {
+                SolarEclipseKind::Total
+            } else {
+                SolarEclipseKind::Annular
+            }
+        } else {
+            let magnitude = (1.5433 + u - gamma) / (0.5461 + 2.0 * u);
+            return Some((greatest, EclipseKind::Solar(SolarEclipseKind::Partial), magnitude));
+        };
+        // Ratio of the apparent diameters of the Moon, seen from the Earth's surface below it,
+        // and the Sun
+        let lunar_semidiameter = arcsin(1737400.0 / (lunar_distance(greatest, dt) - 6378140.0));
+        let solar_semidiameter = arcsin(696000000.0 / solar_distance(greatest, dt));
+        Some((greatest, EclipseKind::Solar(kind), lunar_semidiameter / solar_semidiameter))
+    }
+}
+
+/// Returns an iterator of lunar and solar eclipses.
+///
+/// Each full moon and new moon in `range` (as found by
+/// [`lunar_phase_iter`](crate::lunar_phase_iter)) is checked for an
+/// eclipse. The moment of greatest eclipse may differ from the moment of the full or new moon by
+/// up to a few hours.
+///
+/// ```
+/// use esbat::{EclipseKind, FixedDate, LunarEclipseKind, SolarEclipseKind};
+///
+/// let start = FixedDate::from_gregorian(2017, 1, 1).unwrap().moment();
+/// let end = FixedDate::from_gregorian(2018, 1, 1).unwrap().moment();
+/// let mut iter = esbat::eclipse_iter(start..end);
+///
+/// assert_eq!(iter.next().unwrap().kind, EclipseKind::Lunar(LunarEclipseKind::Penumbral));
+/// assert_eq!(iter.next().unwrap().kind, EclipseKind::Solar(SolarEclipseKind::Annular));
+/// assert_eq!(iter.next().unwrap().kind, EclipseKind::Lunar(LunarEclipseKind::Partial));
+///
+/// let eclipse = iter.next().unwrap();
+/// assert_eq!(eclipse.kind, EclipseKind::Solar(SolarEclipseKind::Total));
+/// assert_eq!(eclipse.greatest.fixed_date(), FixedDate::from_gregorian(2017, 8, 21).unwrap());
+/// assert!((eclipse.magnitude - 1.03).abs() < 0.01);
+///
+/// assert!(iter.next().is_none());
+/// ```
+pub fn eclipse_iter<T, B>(range: B) -> EclipseIter<T>
+where
+    T: Timestamp,
+    B: RangeBounds<T>,
+{
+    let (start, end) = moment_bounds(&range);
+    EclipseIter::new(start, end, EspenakMeeus)
+}
+
+/// Eclipse iterator.
+///
+/// This struct is created by [`eclipse_iter`] or
+/// [`Ephemeris::eclipse_iter`](crate::Ephemeris::eclipse_iter), which determines the ΔT model `M`.
+#[derive(Debug, Clone)]
+pub struct EclipseIter<T = Moment, M = EspenakMeeus> {
+    inner: Iter<T, M>,
+    model: M,
+}
+
+impl<T: Timestamp, M: DeltaT + Clone> EclipseIter<T, M> {
+    pub(crate) fn new(start: (f64, bool), end: (f64, bool), model: M) -> EclipseIter<T, M> {
+        EclipseIter { inner: Iter::new(start, end, model.clone()), model }
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> Iterator for EclipseIter<T, M> {
+    type Item = Eclipse<T>;
+
+    fn next(&mut self) -> Option<Eclipse<T>> {
+        let k0 = nth_new_moon(24724, &self.model);
+        loop {
+            let (phase, t) = self.inner.next()?;
+            let offset = match phase {
+                PrincipalPhase::NewMoon => 0.0,
+                PrincipalPhase::FullMoon => 0.5,
+                _ => continue,
+            };
+            let k = round((t.to_moment().rd() - k0) / MEAN_SYNODIC_MONTH - offset) + offset;
+            if let Some((greatest, kind, magnitude)) = eclipse(k, &self.model) {
+                if let Some(greatest) = T::from_moment(Moment::from_rd(greatest)) {
+                    return Some(Eclipse { kind, greatest, magnitude });
+                }
+            }
+        }
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> FusedIterator for EclipseIter<T, M> {}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_eclipses() {
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use EclipseKind::{Lunar, Solar};
+
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let expected = [
+        (Lunar(LunarEclipseKind::Penumbral), date(2020, 1, 10)),
+        (Lunar(LunarEclipseKind::Penumbral), date(2020, 6, 5)),
+        (Solar(SolarEclipseKind::Annular), date(2020, 6, 21)),
+        (Lunar(LunarEclipseKind::Penumbral), date(2020, 7, 5)),
+        (Lunar(LunarEclipseKind::Penumbral), date(2020, 11, 30)),
+        (Solar(SolarEclipseKind::Total), date(2020, 12, 14)),
+        (Lunar(LunarEclipseKind::Total), date(2021, 5, 26)),
+        (Solar(SolarEclipseKind::Annular), date(2021, 6, 10)),
+        (Lunar(LunarEclipseKind::Partial), date(2021, 11, 19)),
+        (Solar(SolarEclipseKind::Total), date(2021, 12, 4)),
+        (Solar(SolarEclipseKind::Partial), date(2022, 4, 30)),
+        (Lunar(LunarEclipseKind::Total), date(2022, 5, 16)),
+        (Solar(SolarEclipseKind::Partial), date(2022, 10, 25)),
+        (Lunar(LunarEclipseKind::Total), date(2022, 11, 8)),
+        (Solar(SolarEclipseKind::Hybrid), date(2023, 4, 20)),
+        (Lunar(LunarEclipseKind::Penumbral), date(2023, 5, 5)),
+        (Solar(SolarEclipseKind::Annular), date(2023, 10, 14)),
+        (Lunar(LunarEclipseKind::Partial), date(2023, 10, 28)),
+    ];
+    let actual =
+        eclipse_iter(start..end).map(|e| (e.kind, e.greatest.date_naive())).collect::<Vec<_>>();
+    assert_eq!(actual, expected);
+
+    // Reverse iteration finds the same eclipses
+    assert_eq!(eclipse_iter(end..start).count(), expected.len());
+}
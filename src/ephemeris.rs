@@ -0,0 +1,571 @@
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+
+use crate::apsis::{moon_size_with, ApsisIter, MoonSize, SupermoonRule};
+use crate::babylonian::BabylonianDate;
+use crate::blue::{second_in_month, third_of_four_in_season, FULL_MOON, NEW_MOON};
+use crate::calendar;
+use crate::chinese::{
+    chinese_new_year_with, mid_autumn_festival_with, CelestialStem, ChineseDate, EarthlyBranch,
+    SolarTermIter,
+};
+use crate::delta_t::{DeltaT, EspenakMeeus};
+use crate::easter::{easter_with, Computus};
+use crate::eclipse::EclipseIter;
+use crate::islamic::{ramadan_with, CrescentCriterion, IslamicCalendar, IslamicDate};
+use crate::iter::{day_bounds, local_day_bounds, moment_bounds, DailyIter, Iter};
+use crate::location::{Hemisphere, Location};
+use crate::lunation::{Lunation, LunationIter, LunationNumbering};
+use crate::moment::{CivilDate, Day, LocalDate, Moment, Time, Timestamp};
+use crate::name::{full_moon_name_with, FullMoonName, FullMoonTradition};
+use crate::panchanga::{panchanga_with, Ayanamsha, Panchanga, TithiIter};
+use crate::phase::Phase;
+use crate::rise::{on_day, Twilight};
+use crate::sabbat::{SabbatIter, SabbatMode, WheelIter};
+use crate::season::SeasonIter;
+use crate::state::{lunar_state_with, LunarState};
+use crate::util::clamp_angle;
+use core::ops::RangeBounds;
+
+/// Calculations of the positions of the Sun and Moon with a chosen [`DeltaT`] model.
+///
+/// The methods of `Ephemeris` are the same as the functions of the same names in this crate, which
+/// use [`EspenakMeeus`]. An `Ephemeris` can use any model, including a reference to one, such as a
+/// [`DeltaTTable`](crate::DeltaTTable) shared between several calculations. Iterators created by an
+/// `Ephemeris` borrow its model.
+///
+/// ```
+/// use esbat::{Ephemeris, FixedDate, FixedDeltaT, MorrisonStephenson2004};
+///
+/// let t = FixedDate::from_gregorian(2020, 10, 1).unwrap().moment();
+/// assert_eq!(Ephemeris::default().lunar_phase(t), esbat::lunar_phase(t));
+///
+/// // Treating Universal Time as Terrestrial Time delays the full moon by ΔT, about 71.6 seconds
+/// let full_moon = Ephemeris::default().lunar_phase_at_or_after(180.0, t).unwrap();
+/// let tt = Ephemeris::new(FixedDeltaT(0.0)).lunar_phase_at_or_after(180.0, t).unwrap();
+/// assert!(((tt.rd() - full_moon.rd()) * 86400.0 - 71.6).abs() < 0.1);
+///
+/// let ancient = Ephemeris::new(MorrisonStephenson2004);
+/// let t = FixedDate::from_gregorian(-1000, 1, 1).unwrap().moment();
+/// assert!((ancient.delta_t(t) - 25428.0).abs() < 10.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ephemeris<M = EspenakMeeus> {
+    model: M,
+}
+
+impl<M: DeltaT> Ephemeris<M> {
+    /// Creates an ephemeris that uses `model` for ΔT.
+    pub fn new(model: M) -> Ephemeris<M> {
+        Ephemeris { model }
+    }
+
+    /// Returns the ΔT model.
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    /// Returns ΔT in seconds at moment `t`.
+    pub fn delta_t<T: Time>(&self, t: T) -> f64 {
+        self.model.delta_t(t.to_moment())
+    }
+
+    /// Calculates the lunar phase for a given moment. See [`lunar_phase`](crate::lunar_phase).
+    pub fn lunar_phase<T: Time>(&self, t: T) -> f64 {
+        calendar::lunar_phase(t.to_moment().rd(), &self.model)
+    }
+
+    /// Calculates the lunar phase for a given date. See
+    /// [`daily_lunar_phase`](crate::daily_lunar_phase).
+    pub fn daily_lunar_phase<D: Day>(&self, t: D) -> Phase {
+        let (start, end) = t.bounds();
+        Phase::from_phase_range(self.lunar_phase(start), self.lunar_phase(end))
+    }
+
+    /// Calculates the solar longitude for a given moment. See
+    /// [`solar_longitude`](crate::solar_longitude).
+    pub fn solar_longitude<T: Time>(&self, t: T) -> f64 {
+        calendar::solar_longitude(t.to_moment().rd(), &self.model)
+    }
+
+    /// Calculates the illuminated fraction of the Moon's disc for a given moment. See
+    /// [`lunar_illumination`](crate::lunar_illumination).
+    pub fn lunar_illumination<T: Time>(&self, t: T) -> f64 {
+        calendar::lunar_illumination(t.to_moment().rd(), &self.model)
+    }
+
+    /// Calculates the distance between the centers of the Earth and the Moon for a given moment,
+    /// in kilometers. See [`lunar_distance`](crate::lunar_distance).
+    pub fn lunar_distance<T: Time>(&self, t: T) -> f64 {
+        calendar::lunar_distance(t.to_moment().rd(), &self.model) / 1000.0
+    }
+
+    /// Calculates the position angle of the Moon's bright limb for a given moment. See
+    /// [`bright_limb_angle`](crate::bright_limb_angle).
+    pub fn bright_limb_angle<T: Time>(&self, t: T) -> f64 {
+        calendar::bright_limb_angle(t.to_moment().rd(), &self.model)
+    }
+
+    /// Finds the first moment at or after `t` when the lunar phase is `phase` degrees.
+    ///
+    /// The moment is returned in the time zone of `t`. Returns `None` if the moment cannot be
+    /// represented by the type of `t`.
+    pub fn lunar_phase_at_or_after<T: Time>(&self, phase: f64, t: T) -> Option<T> {
+        let next =
+            calendar::lunar_phase_at_or_after(clamp_angle(phase), t.to_moment().rd(), &self.model);
+        t.with_moment(Moment::from_rd(next))
+    }
+
+    /// Finds the last moment at or before `t` when the lunar phase is `phase` degrees.
+    ///
+    /// The moment is returned in the time zone of `t`. Returns `None` if the moment cannot be
+    /// represented by the type of `t`.
+    pub fn lunar_phase_at_or_before<T: Time>(&self, phase: f64, t: T) -> Option<T> {
+        let prev =
+            calendar::lunar_phase_at_or_before(clamp_angle(phase), t.to_moment().rd(), &self.model);
+        t.with_moment(Moment::from_rd(prev))
+    }
+
+    /// Finds the first moment at or after `t` when the solar longitude is `angle` degrees. See
+    /// [`solar_longitude_after`](crate::solar_longitude_after).
+    pub fn solar_longitude_after<T: Time>(&self, angle: f64, t: T) -> Option<T> {
+        let next =
+            calendar::solar_longitude_after(clamp_angle(angle), t.to_moment().rd(), &self.model);
+        t.with_moment(Moment::from_rd(next))
+    }
+
+    /// Returns an iterator of principal phases and their moments. See
+    /// [`lunar_phase_iter`](crate::lunar_phase_iter).
+    pub fn lunar_phase_iter<T, B>(&self, range: B) -> Iter<T, &M>
+    where
+        T: Timestamp,
+        B: RangeBounds<T>,
+    {
+        let (start, end) = moment_bounds(&range);
+        Iter::new(start, end, &self.model)
+    }
+
+    /// Returns an iterator of principal phases and the days they fall on. See
+    /// [`daily_lunar_phase_iter`](crate::daily_lunar_phase_iter).
+    pub fn daily_lunar_phase_iter<D, B>(&self, range: B) -> DailyIter<D, &M>
+    where
+        D: CivilDate,
+        B: RangeBounds<D>,
+    {
+        let (start, end, zone) = day_bounds(&range);
+        DailyIter::new(start, end, zone, &self.model)
+    }
+
+    /// Returns an iterator of principal phases and the days they fall on in the time zone `zone`.
+    /// See [`daily_lunar_phase_iter_in`](crate::daily_lunar_phase_iter_in).
+    pub fn daily_lunar_phase_iter_in<D, Z, B>(&self, range: B, zone: Z) -> DailyIter<D::Local, &M>
+    where
+        D: LocalDate<Z>,
+        B: RangeBounds<D>,
+    {
+        let (start, end, template) = local_day_bounds(&range, &zone);
+        DailyIter::new(start, end, template, &self.model)
+    }
+
+    /// Returns a snapshot of the Moon's state at a moment. See [`lunar_state`](crate::lunar_state).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the surrounding principal phases are outside of the range of times the type of
+    /// `t` can represent.
+    pub fn lunar_state<T: Time>(&self, t: T) -> LunarState<T> {
+        lunar_state_with(t, &self.model)
+    }
+
+    /// Returns an iterator of equinoxes and solstices and their moments. See
+    /// [`season_iter`](crate::season_iter).
+    pub fn season_iter<T, B>(&self, range: B) -> SeasonIter<T, &M>
+    where
+        T: Timestamp,
+        B: RangeBounds<T>,
+    {
+        let (start, end) = moment_bounds(&range);
+        SeasonIter::new(start, end, &self.model)
+    }
+
+    /// Returns an iterator of sabbats and their moments. See [`sabbat_iter`](crate::sabbat_iter).
+    pub fn sabbat_iter<T, B>(
+        &self,
+        range: B,
+        mode: SabbatMode,
+        hemisphere: Hemisphere,
+    ) -> SabbatIter<T, &M>
+    where
+        T: Timestamp,
+        B: RangeBounds<T>,
+    {
+        let (start, end) = moment_bounds(&range);
+        SabbatIter::new(start, end, mode, hemisphere, &self.model)
+    }
+
+    /// Returns an iterator of sabbats and principal phases, in order, and their moments. See
+    /// [`wheel_iter`](crate::wheel_iter).
+    pub fn wheel_iter<T, B>(
+        &self,
+        range: B,
+        mode: SabbatMode,
+        hemisphere: Hemisphere,
+    ) -> WheelIter<T, &M>
+    where
+        T: Timestamp,
+        B: RangeBounds<T>,
+    {
+        let (start, end) = moment_bounds(&range);
+        WheelIter::new(start, end, mode, hemisphere, &self.model)
+    }
+
+    /// Returns an iterator of lunar perigees and apogees and their moments. See
+    /// [`apsis_iter`](crate::apsis_iter).
+    pub fn apsis_iter<T, B>(&self, range: B) -> ApsisIter<T, &M>
+    where
+        T: Timestamp,
+        B: RangeBounds<T>,
+    {
+        let (start, end) = moment_bounds(&range);
+        ApsisIter::new(start, end, &self.model)
+    }
+
+    /// Determines whether a full or new moon is a supermoon or micromoon. See
+    /// [`moon_size`](crate::moon_size).
+    pub fn moon_size<T: Time>(&self, t: T, rule: SupermoonRule) -> Option<MoonSize> {
+        moon_size_with(t, rule, &self.model)
+    }
+
+    /// Returns an iterator of lunar and solar eclipses. See [`eclipse_iter`](crate::eclipse_iter).
+    pub fn eclipse_iter<T, B>(&self, range: B) -> EclipseIter<T, &M>
+    where
+        T: Timestamp,
+        B: RangeBounds<T>,
+    {
+        let (start, end) = moment_bounds(&range);
+        EclipseIter::new(start, end, &self.model)
+    }
+
+    /// Returns the lunation containing moment `t`. See [`Lunation::containing`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lunation begins or ends outside of the range of times the type of `t` can
+    /// represent.
+    pub fn lunation_containing<T: Time>(&self, t: T) -> Lunation<T> {
+        Lunation::containing_with(t, &self.model)
+    }
+
+    /// Returns the lunation with the given number in a numbering convention. See
+    /// [`Lunation::from_number`].
+    pub fn lunation_from_number<T: Timestamp>(
+        &self,
+        number: i32,
+        numbering: LunationNumbering,
+    ) -> Option<Lunation<T>> {
+        Lunation::from_number_with(number, numbering, &self.model)
+    }
+
+    /// Returns the lunation after `lunation`. See [`Lunation::succ`].
+    pub fn lunation_succ<T: Time>(&self, lunation: &Lunation<T>) -> Option<Lunation<T>> {
+        lunation.succ_with(&self.model)
+    }
+
+    /// Returns the lunation before `lunation`. See [`Lunation::pred`].
+    pub fn lunation_pred<T: Time>(&self, lunation: &Lunation<T>) -> Option<Lunation<T>> {
+        lunation.pred_with(&self.model)
+    }
+
+    /// Returns an iterator of the lunations that begin within a range. See
+    /// [`lunation_iter`](crate::lunation_iter).
+    pub fn lunation_iter<T, B>(&self, range: B) -> LunationIter<T, &M>
+    where
+        T: Timestamp,
+        B: RangeBounds<T>,
+    {
+        let (start, end) = moment_bounds(&range);
+        LunationIter::new(start, end, &self.model)
+    }
+
+    /// Returns an iterator of solar terms and their moments. See
+    /// [`solar_term_iter`](crate::solar_term_iter).
+    pub fn solar_term_iter<T, B>(&self, range: B) -> SolarTermIter<T, &M>
+    where
+        T: Timestamp,
+        B: RangeBounds<T>,
+    {
+        let (start, end) = moment_bounds(&range);
+        SolarTermIter::new(start, end, &self.model)
+    }
+
+    /// Returns the tithi, nakshatra, yoga and karana at a moment. See
+    /// [`panchanga`](crate::panchanga()).
+    pub fn panchanga<T: Time>(&self, t: T, ayanamsha: Ayanamsha) -> Panchanga {
+        panchanga_with(t, ayanamsha, &self.model)
+    }
+
+    /// Returns an iterator of tithis and the moments they begin. See
+    /// [`tithi_iter`](crate::tithi_iter).
+    pub fn tithi_iter<T, B>(&self, range: B) -> TithiIter<T, &M>
+    where
+        T: Timestamp,
+        B: RangeBounds<T>,
+    {
+        let (start, end) = moment_bounds(&range);
+        TithiIter::new(start, end, &self.model)
+    }
+
+    /// Calculates the moment of moonrise on a given date at a given location. See
+    /// [`moonrise`](crate::moonrise).
+    pub fn moonrise<D: Day>(&self, date: D, location: Location) -> Option<D::Time> {
+        on_day(date, |start, end| calendar::moonrise(start, end, location, &self.model))
+    }
+
+    /// Calculates the moment of moonset on a given date at a given location. See
+    /// [`moonset`](crate::moonset).
+    pub fn moonset<D: Day>(&self, date: D, location: Location) -> Option<D::Time> {
+        on_day(date, |start, end| calendar::moonset(start, end, location, &self.model))
+    }
+
+    /// Calculates the moment of sunrise on a given date at a given location. See
+    /// [`sunrise`](crate::sunrise).
+    pub fn sunrise<D: Day>(&self, date: D, location: Location) -> Option<D::Time> {
+        on_day(date, |start, end| calendar::sunrise(start, end, location, &self.model))
+    }
+
+    /// Calculates the moment of sunset on a given date at a given location. See
+    /// [`sunset`](crate::sunset).
+    pub fn sunset<D: Day>(&self, date: D, location: Location) -> Option<D::Time> {
+        on_day(date, |start, end| calendar::sunset(start, end, location, &self.model))
+    }
+
+    /// Calculates the moment of dawn on a given date at a given location. See
+    /// [`dawn`](crate::dawn).
+    pub fn dawn<D: Day>(&self, date: D, location: Location, twilight: Twilight) -> Option<D::Time> {
+        on_day(date, |start, end| {
+            calendar::dawn(start, end, location, twilight.as_angle(), &self.model)
+        })
+    }
+
+    /// Calculates the moment of dusk on a given date at a given location. See
+    /// [`dusk`](crate::dusk).
+    pub fn dusk<D: Day>(&self, date: D, location: Location, twilight: Twilight) -> Option<D::Time> {
+        on_day(date, |start, end| {
+            calendar::dusk(start, end, location, twilight.as_angle(), &self.model)
+        })
+    }
+
+    /// Returns true if the crescent moon is predicted to be visible to the naked eye at a location
+    /// on the evening of `date`. See [`is_crescent_visible`](crate::is_crescent_visible).
+    pub fn is_crescent_visible<D: Day>(
+        &self,
+        date: D,
+        location: Location,
+        criterion: CrescentCriterion,
+    ) -> bool {
+        criterion.visible(date.fixed_date().rd(), location, &self.model)
+    }
+
+    /// Converts a date in the proleptic Gregorian calendar to a form of the Islamic calendar. See
+    /// [`IslamicDate::from_gregorian`].
+    pub fn islamic_from_gregorian<D: Day>(
+        &self,
+        date: D,
+        calendar: IslamicCalendar,
+    ) -> Option<IslamicDate> {
+        IslamicDate::from_gregorian_with(date, calendar, &self.model)
+    }
+
+    /// Converts a date in a form of the Islamic calendar to the proleptic Gregorian calendar. See
+    /// [`IslamicDate::to_gregorian`].
+    pub fn islamic_to_gregorian<D: CivilDate>(
+        &self,
+        date: IslamicDate,
+        calendar: IslamicCalendar,
+    ) -> Option<D> {
+        date.to_gregorian_with(calendar, &self.model)
+    }
+
+    /// Predicts the first day of Ramadan and the first day of Shawwal in an Islamic year. See
+    /// [`ramadan`](crate::ramadan).
+    pub fn ramadan<D: CivilDate>(&self, year: i32, calendar: IslamicCalendar) -> Option<(D, D)> {
+        ramadan_with(year, calendar, &self.model)
+    }
+
+    /// Converts a date in the proleptic Gregorian calendar to the Babylonian calendar. See
+    /// [`BabylonianDate::from_gregorian`].
+    pub fn babylonian_from_gregorian<D: Day>(&self, date: D) -> Option<BabylonianDate> {
+        BabylonianDate::from_gregorian_with(date, &self.model)
+    }
+
+    /// Converts a date in the Babylonian calendar to the proleptic Gregorian calendar. See
+    /// [`BabylonianDate::to_gregorian`].
+    pub fn babylonian_to_gregorian<D: CivilDate>(&self, date: BabylonianDate) -> Option<D> {
+        date.to_gregorian_with(&self.model)
+    }
+
+    /// Converts a date in the proleptic Gregorian calendar to the Chinese calendar. See
+    /// [`ChineseDate::from_gregorian`].
+    pub fn chinese_from_gregorian<D: Day>(&self, date: D) -> ChineseDate {
+        ChineseDate::from_gregorian_with(date, &self.model)
+    }
+
+    /// Converts a date in the Chinese calendar to the proleptic Gregorian calendar. See
+    /// [`ChineseDate::to_gregorian`].
+    pub fn chinese_to_gregorian<D: CivilDate>(&self, date: ChineseDate) -> Option<D> {
+        date.to_gregorian_with(&self.model)
+    }
+
+    /// Returns the sexagenary name of a day in the Chinese calendar. See
+    /// [`ChineseDate::day_name`].
+    pub fn chinese_day_name(&self, date: ChineseDate) -> (CelestialStem, EarthlyBranch) {
+        date.day_name_with(&self.model)
+    }
+
+    /// Returns the date of the Chinese New Year in a Gregorian year. See
+    /// [`chinese_new_year`](crate::chinese_new_year).
+    pub fn chinese_new_year<D: CivilDate>(&self, year: i32) -> Option<D> {
+        chinese_new_year_with(year, &self.model)
+    }
+
+    /// Returns the date of the Mid-Autumn Festival in a Gregorian year. See
+    /// [`mid_autumn_festival`](crate::mid_autumn_festival).
+    pub fn mid_autumn_festival<D: CivilDate>(&self, year: i32) -> Option<D> {
+        mid_autumn_festival_with(year, &self.model)
+    }
+
+    /// Returns true if the full moon at moment `t` is a blue moon. See
+    /// [`is_blue_moon`](crate::is_blue_moon).
+    pub fn is_blue_moon<T: Time>(&self, t: T) -> bool {
+        second_in_month(FULL_MOON, t, &self.model)
+    }
+
+    /// Returns true if the full moon at moment `t` is a seasonal blue moon. See
+    /// [`is_seasonal_blue_moon`](crate::is_seasonal_blue_moon).
+    pub fn is_seasonal_blue_moon<T: Time>(&self, t: T) -> bool {
+        third_of_four_in_season(FULL_MOON, t, &self.model)
+    }
+
+    /// Returns true if the new moon at moment `t` is a black moon. See
+    /// [`is_black_moon`](crate::is_black_moon).
+    pub fn is_black_moon<T: Time>(&self, t: T) -> bool {
+        second_in_month(NEW_MOON, t, &self.model)
+    }
+
+    /// Returns true if the new moon at moment `t` is a seasonal black moon. See
+    /// [`is_seasonal_black_moon`](crate::is_seasonal_black_moon).
+    pub fn is_seasonal_black_moon<T: Time>(&self, t: T) -> bool {
+        third_of_four_in_season(NEW_MOON, t, &self.model)
+    }
+
+    /// Returns the traditional name of the full moon at moment `t`. See
+    /// [`full_moon_name`](crate::full_moon_name).
+    pub fn full_moon_name<T: Time>(
+        &self,
+        t: T,
+        tradition: FullMoonTradition,
+        hemisphere: Hemisphere,
+    ) -> FullMoonName {
+        full_moon_name_with(t, tradition, hemisphere, &self.model)
+    }
+
+    /// Returns the date of Easter Sunday in a year of the Gregorian calendar. See
+    /// [`easter`](crate::easter).
+    pub fn easter<D: CivilDate>(&self, year: i32, computus: Computus) -> Option<D> {
+        easter_with(year, computus, &self.model)
+    }
+}
+
+impl Default for Ephemeris {
+    fn default() -> Ephemeris {
+        Ephemeris::new(EspenakMeeus)
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_ephemeris() {
+    use crate::delta_t::FixedDeltaT;
+    use chrono::{TimeZone, Utc};
+
+    let t = Utc.with_ymd_and_hms(1992, 4, 11, 23, 59, 1).unwrap();
+    let ephemeris = Ephemeris::default();
+    assert!((ephemeris.lunar_phase(t) - crate::lunar_phase(t)).abs() < f64::EPSILON);
+    assert!((ephemeris.lunar_distance(t) - crate::lunar_distance(t)).abs() < f64::EPSILON);
+    assert!((ephemeris.bright_limb_angle(t) - crate::bright_limb_angle(t)).abs() < f64::EPSILON);
+    assert_eq!(ephemeris.solar_longitude_after(90.0, t), crate::solar_longitude_after(90.0, t));
+
+    // A model shared by reference gives the same results as the model itself
+    let model = FixedDeltaT(120.0);
+    let by_ref = Ephemeris::new(&model);
+    let owned = Ephemeris::new(model);
+    assert!((by_ref.lunar_illumination(t) - owned.lunar_illumination(t)).abs() < f64::EPSILON);
+
+    // Events in Terrestrial Time are moved earlier in Universal Time by a larger ΔT
+    let full_moon = |dt| Ephemeris::new(FixedDeltaT(dt)).lunar_phase_at_or_after(180.0, t).unwrap();
+    let shift = full_moon(0.0).signed_duration_since(full_moon(3600.0)).num_milliseconds();
+    assert!((shift - 3_600_000).abs() < 1000);
+    let prev = owned.lunar_phase_at_or_before(180.0, t).unwrap();
+    assert!((owned.lunar_phase(prev) - 180.0).abs() < 0.0001);
+}
+
+#[cfg(test)]
+#[test]
+fn test_ephemeris_iter() {
+    use crate::delta_t::FixedDeltaT;
+    use crate::moment::FixedDate;
+
+    let moment = |y, m, d| FixedDate::from_gregorian(y, m, d).unwrap().moment();
+    let range = moment(2020, 1, 1)..moment(2021, 1, 1);
+    let ephemeris = Ephemeris::default();
+    assert!(ephemeris.lunar_phase_iter(range.clone()).eq(crate::lunar_phase_iter(range.clone())));
+    assert!(ephemeris.season_iter(range.clone()).eq(crate::season_iter(range.clone())));
+    assert!(ephemeris.apsis_iter(range.clone()).eq(crate::apsis_iter(range.clone())));
+    assert!(ephemeris.eclipse_iter(range.clone()).eq(crate::eclipse_iter(range.clone())));
+    assert!(ephemeris.lunation_iter(range.clone()).eq(crate::lunation_iter(range.clone())));
+    assert!(ephemeris.tithi_iter(range.clone()).eq(crate::tithi_iter(range.clone())));
+    assert!(ephemeris.solar_term_iter(range.clone()).eq(crate::solar_term_iter(range.clone())));
+    let date = |m, d| FixedDate::from_gregorian(2020, m, d).unwrap();
+    assert!(ephemeris
+        .daily_lunar_phase_iter(date(1, 1)..date(12, 31))
+        .eq(crate::daily_lunar_phase_iter(date(1, 1)..date(12, 31))));
+    assert_eq!(
+        ephemeris.easter::<FixedDate>(2019, Computus::Astronomical),
+        crate::easter(2019, Computus::Astronomical)
+    );
+    assert_eq!(ephemeris.chinese_new_year::<FixedDate>(2020), crate::chinese_new_year(2020));
+    assert_eq!(
+        ephemeris.chinese_from_gregorian(date(6, 1)),
+        ChineseDate::from_gregorian(date(6, 1))
+    );
+    for (phase, t) in crate::lunar_phase_iter(range.clone()) {
+        assert_eq!(ephemeris.is_blue_moon(t), crate::is_blue_moon(t));
+        assert_eq!(ephemeris.is_seasonal_black_moon(t), crate::is_seasonal_black_moon(t));
+        if phase == crate::PrincipalPhase::FullMoon {
+            let (tradition, hemisphere) =
+                (FullMoonTradition::OldFarmersAlmanac, Hemisphere::Northern);
+            assert_eq!(
+                ephemeris.full_moon_name(t, tradition, hemisphere),
+                crate::full_moon_name(t, tradition, hemisphere)
+            );
+        }
+    }
+
+    // Every event found with a larger ΔT is earlier by the difference
+    let tt = Ephemeris::new(FixedDeltaT(0.0));
+    let ut = Ephemeris::new(FixedDeltaT(3600.0));
+    for ((a, t), (b, u)) in tt.season_iter(range.clone()).zip(ut.season_iter(range.clone())) {
+        assert_eq!(a, b);
+        assert!((t.rd() - u.rd() - 1.0 / 24.0).abs() < 1.0 / 86400.0);
+    }
+    let lunation = tt.lunation_containing(moment(2020, 6, 1));
+    let shifted = ut.lunation_containing(moment(2020, 6, 1));
+    assert!((lunation.start().rd() - shifted.start().rd() - 1.0 / 24.0).abs() < 1.0 / 86400.0);
+    let next = tt.lunation_succ(&lunation).unwrap();
+    assert_eq!(tt.lunation_pred(&next), Some(lunation));
+    assert_eq!(next.start(), lunation.end());
+}
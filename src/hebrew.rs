@@ -0,0 +1,430 @@
+// Copyright (c) 2018 Edward M. Reingold and Nachum Dershowitz
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+//
+// Functions from chapter 8, "The Hebrew Calendar", of:
+//
+//     E. M. Reingold and N. Dershowitz, Calendrical Calculations: The Ultimate Edition.
+//         Cambridge University Press, 2018. doi:10.1017/9781107415058
+//
+// See `calendar.rs` for the representation of dates and moments. Months are numbered from Nisan
+// (1) to Adar (12), with Adar II (13) in leap years; the year begins with Tishri (7).
+//
+// The molad is counted in parts (<i>halakim</i>) to avoid rounding error; there are 1080 parts in
+// an hour.
+
+#![allow(clippy::unreadable_literal)]
+
+use crate::moment::{CivilDate, Day, FixedDate, Moment, Timestamp, Weekday, WEEKDAYS};
+use crate::util::{floor, i32, nonneg};
+
+// fixed_from_julian(-3761, 10, 7)
+const HEBREW_EPOCH: i32 = -1373427;
+
+const NISAN: u32 = 1;
+const IYYAR: u32 = 2;
+const SIVAN: u32 = 3;
+const TAMMUZ: u32 = 4;
+const AV: u32 = 5;
+const ELUL: u32 = 6;
+const TISHRI: u32 = 7;
+const MARHESHVAN: u32 = 8;
+const KISLEV: u32 = 9;
+const TEVET: u32 = 10;
+const ADAR: u32 = 12;
+const ADAR_II: u32 = 13;
+
+const PARTS_PER_DAY: i64 = 25920;
+// 29 days, 12 hours, and 793 parts
+const MEAN_MONTH_PARTS: i64 = 765433;
+
+// The longitude of Jerusalem, in degrees
+const JERUSALEM_LONGITUDE: f64 = 35.24;
+
+fn is_hebrew_leap_year(year: i32) -> bool {
+    (7 * i64::from(year) + 1).rem_euclid(19) < 7
+}
+
+fn last_month_of_hebrew_year(year: i32) -> u32 {
+    if is_hebrew_leap_year(year) {
+        ADAR_II
+    } else {
+        ADAR
+    }
+}
+
+fn months_elapsed(year: i32) -> i64 {
+    (235 * i64::from(year) - 234).div_euclid(19)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn hebrew_calendar_elapsed_days(year: i32) -> i32 {
+    let months_elapsed = months_elapsed(year);
+    let parts_elapsed = 12084 + 13753 * months_elapsed;
+    let days = 29 * months_elapsed + parts_elapsed.div_euclid(PARTS_PER_DAY);
+    (if (3 * (days + 1)).rem_euclid(7) < 3 { days + 1 } else { days }) as i32
+}
+
+fn hebrew_year_length_correction(year: i32) -> i32 {
+    let ny0 = hebrew_calendar_elapsed_days(year - 1);
+    let ny1 = hebrew_calendar_elapsed_days(year);
+    let ny2 = hebrew_calendar_elapsed_days(year + 1);
+    if ny2 - ny1 == 356 {
+        2
+    } else {
+        i32::from(ny1 - ny0 == 382)
+    }
+}
+
+fn hebrew_new_year(year: i32) -> i32 {
+    HEBREW_EPOCH + hebrew_calendar_elapsed_days(year) + hebrew_year_length_correction(year)
+}
+
+fn days_in_hebrew_year(year: i32) -> i32 {
+    hebrew_new_year(year + 1) - hebrew_new_year(year)
+}
+
+fn is_long_marheshvan(year: i32) -> bool {
+    [355, 385].contains(&days_in_hebrew_year(year))
+}
+
+fn is_short_kislev(year: i32) -> bool {
+    [353, 383].contains(&days_in_hebrew_year(year))
+}
+
+fn last_day_of_hebrew_month(month: u32, year: i32) -> i32 {
+    if [IYYAR, TAMMUZ, ELUL, TEVET, ADAR_II].contains(&month)
+        || (month == MARHESHVAN && !is_long_marheshvan(year))
+        || (month == KISLEV && is_short_kislev(year))
+        || (month == ADAR && !is_hebrew_leap_year(year))
+    {
+        29
+    } else {
+        30
+    }
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn fixed_from_hebrew(year: i32, month: u32, day: u32) -> i32 {
+    let days_before_month: i32 = if month < TISHRI {
+        (TISHRI..=last_month_of_hebrew_year(year))
+            .chain(NISAN..month)
+            .map(|m| last_day_of_hebrew_month(m, year))
+            .sum()
+    } else {
+        (TISHRI..month).map(|m| last_day_of_hebrew_month(m, year)).sum()
+    };
+    hebrew_new_year(year) + day as i32 - 1 + days_before_month
+}
+
+fn hebrew_from_fixed(date: i32) -> (i32, u32, u32) {
+    let approx = i32(floor(f64::from(date - HEBREW_EPOCH) / (35975351.0 / 98496.0))) + 1;
+    let mut year = approx - 1;
+    while hebrew_new_year(year + 1) <= date {
+        year += 1;
+    }
+    let start = if date < fixed_from_hebrew(year, NISAN, 1) { TISHRI } else { NISAN };
+    let mut month = start;
+    while date > fixed_from_hebrew(year, month, nonneg(last_day_of_hebrew_month(month, year))) {
+        month += 1;
+    }
+    let day = nonneg(date - fixed_from_hebrew(year, month, 1) + 1);
+    (year, month, day)
+}
+
+// The molad of `month` in `year`, in parts since midnight at the start of R.D. 0, in Jerusalem
+// mean time
+fn molad_parts(month: u32, year: i32) -> i64 {
+    let y = if month < TISHRI { year + 1 } else { year };
+    let months_elapsed = i64::from(month) - i64::from(TISHRI) + months_elapsed(y);
+    i64::from(HEBREW_EPOCH) * PARTS_PER_DAY - 876 + months_elapsed * MEAN_MONTH_PARTS
+}
+
+// =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=
+
+/// A date in the Hebrew calendar.
+///
+/// Months are numbered from Nisan (1) to Adar (12), following the Torah; Adar II (13) occurs only
+/// in leap years. The year begins on the first of Tishri (7).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HebrewDate {
+    /// The year, counted from the creation epoch (<i>anno mundi</i>).
+    pub year: i32,
+    /// The month, from 1 (Nisan) to 13 (Adar II).
+    pub month: u32,
+    /// The day of the month, from 1 to 30.
+    pub day: u32,
+}
+
+impl HebrewDate {
+    /// Converts a date in the proleptic Gregorian calendar to the Hebrew calendar.
+    ///
+    /// The Hebrew day begins at sunset; this returns the Hebrew date that begins at sunset on the
+    /// evening before `date`.
+    ///
+    /// ```
+    /// use esbat::{FixedDate, HebrewDate};
+    ///
+    /// let date = HebrewDate::from_gregorian(FixedDate::from_gregorian(2020, 9, 19).unwrap());
+    /// assert_eq!(date, HebrewDate { year: 5781, month: 7, day: 1 });
+    /// ```
+    pub fn from_gregorian<D: Day>(date: D) -> HebrewDate {
+        let (year, month, day) = hebrew_from_fixed(date.fixed_date().rd());
+        HebrewDate { year, month, day }
+    }
+
+    /// Converts the date to the proleptic Gregorian calendar.
+    ///
+    /// Returns `None` if the month or day does not exist in the Hebrew year, or if the date cannot
+    /// be represented by `D`.
+    ///
+    /// ```
+    /// use esbat::{FixedDate, HebrewDate};
+    ///
+    /// let date = HebrewDate { year: 5780, month: 1, day: 15 };
+    /// assert_eq!(date.to_gregorian(), Some(FixedDate::from_gregorian(2020, 4, 9).unwrap()));
+    /// // 5781 is not a leap year, so it has no Adar II
+    /// assert_eq!(HebrewDate { year: 5781, month: 13, day: 1 }.to_gregorian::<FixedDate>(), None);
+    /// ```
+    pub fn to_gregorian<D: CivilDate>(self) -> Option<D> {
+        if self.month == 0
+            || self.month > last_month_of_hebrew_year(self.year)
+            || self.day == 0
+            || i64::from(self.day) > i64::from(last_day_of_hebrew_month(self.month, self.year))
+        {
+            return None;
+        }
+        D::from_fixed(FixedDate::from_rd(fixed_from_hebrew(self.year, self.month, self.day)))
+    }
+
+    /// Returns true if `year` is a leap year, which has thirteen months.
+    ///
+    /// ```
+    /// # use esbat::HebrewDate;
+    /// assert!(HebrewDate::is_leap_year(5782));
+    /// assert!(!HebrewDate::is_leap_year(5781));
+    /// ```
+    pub fn is_leap_year(year: i32) -> bool {
+        is_hebrew_leap_year(year)
+    }
+
+    /// Returns true if the date is Rosh Chodesh, the new month festival.
+    ///
+    /// Rosh Chodesh is the first day of each month except Tishri (which is Rosh Hashanah), and
+    /// also the 30th day of the preceding month if it has one.
+    ///
+    /// ```
+    /// use esbat::{FixedDate, HebrewDate};
+    ///
+    /// let date = |d| HebrewDate::from_gregorian(FixedDate::from_gregorian(2020, 10, d).unwrap());
+    /// assert!(date(18).is_rosh_chodesh());
+    /// assert!(date(19).is_rosh_chodesh());
+    /// assert!(!date(20).is_rosh_chodesh());
+    /// ```
+    pub fn is_rosh_chodesh(self) -> bool {
+        self.day == 30 || (self.day == 1 && self.month != TISHRI)
+    }
+}
+
+/// The molad (mean conjunction) of a month in the Hebrew calendar.
+///
+/// This struct is created by [`molad`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Molad<T = Moment> {
+    /// The moment of the molad.
+    pub moment: T,
+    /// The day of the week of the molad, in Jerusalem mean time. The molad is traditionally
+    /// announced with the day of the week beginning at midnight.
+    pub weekday: Weekday,
+    /// The hour of the molad in Jerusalem mean time, from 0 to 23.
+    pub hour: u32,
+    /// The minute of the molad.
+    pub minute: u32,
+    /// The number of parts (<i>halakim</i>) past the minute, from 0 to 17. There are 18 parts
+    /// in a minute.
+    pub halakim: u32,
+}
+
+/// Calculates the molad of a month in the Hebrew calendar.
+///
+/// The molad is the mean conjunction of the Moon and the Sun used to compute the Hebrew calendar,
+/// which is traditionally announced before the new month. It is reckoned in the local mean time of
+/// Jerusalem, and may differ from the true new moon by as much as a day.
+///
+/// Returns `None` if the molad cannot be represented by `T`.
+///
+/// ```
+/// use esbat::{Molad, Weekday};
+///
+/// // Molad Tishri of the year 1 (Molad BaHaRaD)
+/// let molad: Molad = esbat::molad(1, 7).unwrap();
+/// assert_eq!(molad.weekday, Weekday::Sunday);
+/// assert_eq!((molad.hour, molad.minute, molad.halakim), (23, 11, 6));
+/// ```
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss, clippy::cast_sign_loss)]
+pub fn molad<T: Timestamp>(year: i32, month: u32) -> Option<Molad<T>> {
+    let parts = molad_parts(month, year);
+    let date = parts.div_euclid(PARTS_PER_DAY);
+    let part_of_day = parts.rem_euclid(PARTS_PER_DAY) as u32;
+    let moment = parts as f64 / PARTS_PER_DAY as f64 - JERUSALEM_LONGITUDE / 360.0;
+    Some(Molad {
+        moment: T::from_moment(Moment::from_rd(moment))?,
+        weekday: WEEKDAYS[date.rem_euclid(7) as usize],
+        hour: part_of_day / 1080,
+        minute: part_of_day % 1080 / 18,
+        halakim: part_of_day % 18,
+    })
+}
+
+/// Holidays of the Hebrew calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HebrewHoliday {
+    /// The new year, on the 1st of Tishri.
+    RoshHashanah,
+    /// The Day of Atonement, on the 10th of Tishri.
+    YomKippur,
+    /// The first day of the Feast of Tabernacles, on the 15th of Tishri.
+    Sukkot,
+    /// The Eighth Day of Assembly, on the 22nd of Tishri.
+    SheminiAtzeret,
+    /// The first day of the Festival of Lights, on the 25th of Kislev.
+    Hanukkah,
+    /// The Feast of Lots, on the 14th of Adar (Adar II in leap years).
+    Purim,
+    /// The first day of Passover, on the 15th of Nisan.
+    Passover,
+    /// The Feast of Weeks, on the 6th of Sivan.
+    Shavuot,
+    /// The fast of the Ninth of Av, postponed to the 10th if the 9th is a Sabbath.
+    TishaBAv,
+}
+
+impl HebrewHoliday {
+    /// Returns the date of the holiday in a Gregorian year.
+    ///
+    /// The Hebrew day begins at sunset; holidays begin on the evening before the date returned.
+    ///
+    /// Returns `None` if the date cannot be represented by `D`.
+    ///
+    /// ```
+    /// use esbat::{FixedDate, HebrewHoliday};
+    ///
+    /// let date = FixedDate::from_gregorian(2020, 9, 28).unwrap();
+    /// assert_eq!(HebrewHoliday::YomKippur.date(2020), Some(date));
+    /// ```
+    pub fn date<D: CivilDate>(self, year: i32) -> Option<D> {
+        // The Hebrew year that begins in the autumn of the Gregorian year
+        let new_year = year.checked_add(3761)?;
+        let (month, day) = match self {
+            HebrewHoliday::RoshHashanah => (TISHRI, 1),
+            HebrewHoliday::YomKippur => (TISHRI, 10),
+            HebrewHoliday::Sukkot => (TISHRI, 15),
+            HebrewHoliday::SheminiAtzeret => (TISHRI, 22),
+            HebrewHoliday::Hanukkah => (KISLEV, 25),
+            HebrewHoliday::Purim => (last_month_of_hebrew_year(new_year - 1), 14),
+            HebrewHoliday::Passover => (NISAN, 15),
+            HebrewHoliday::Shavuot => (SIVAN, 6),
+            HebrewHoliday::TishaBAv => (AV, 9),
+        };
+        let hebrew_year = if (TISHRI..=KISLEV).contains(&month) { new_year } else { new_year - 1 };
+        let mut date = fixed_from_hebrew(hebrew_year, month, day);
+        if self == HebrewHoliday::TishaBAv && date.rem_euclid(7) == 6 {
+            date += 1;
+        }
+        D::from_fixed(FixedDate::from_rd(date))
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_hebrew() {
+    use chrono::NaiveDate;
+
+    // Calendrical Calculations, appendix C
+    let sample = [
+        (3174, 5, 10),
+        (3593, 9, 25),
+        (3831, 7, 3),
+        (3896, 7, 9),
+        (4230, 10, 18),
+        (4336, 3, 4),
+        (4455, 8, 13),
+        (4773, 2, 6),
+        (4856, 2, 23),
+        (4950, 1, 7),
+        (5000, 13, 8),
+        (5048, 1, 21),
+        (5058, 2, 7),
+        (5151, 4, 1),
+        (5196, 11, 7),
+        (5252, 1, 3),
+        (5314, 7, 1),
+        (5320, 12, 27),
+        (5408, 3, 20),
+        (5440, 4, 3),
+        (5476, 5, 5),
+        (5528, 4, 4),
+        (5579, 5, 11),
+        (5599, 1, 12),
+        (5663, 1, 22),
+        (5689, 5, 19),
+        (5702, 7, 8),
+        (5703, 1, 14),
+        (5704, 7, 8),
+        (5752, 13, 12),
+        (5756, 12, 5),
+        (5799, 8, 12),
+        (5854, 5, 5),
+    ];
+    for ((rd, _, _, _, _, _), (year, month, day)) in crate::data::TEST_DATA.iter().zip(&sample) {
+        let rd = i32(*rd);
+        assert_eq!(hebrew_from_fixed(rd), (*year, *month, *day));
+        assert_eq!(fixed_from_hebrew(*year, *month, *day), rd);
+    }
+    let convert = |year, month, day| HebrewDate { year, month, day }.to_gregorian::<NaiveDate>();
+    assert!(convert(5781, 0, 1).is_none());
+    assert!(convert(5781, 13, 1).is_none());
+    assert!(convert(5782, 13, 30).is_none());
+    assert!(convert(5781, 1, 0).is_none());
+    assert!(convert(5782, 13, 29).is_some());
+
+    let holidays = [
+        (HebrewHoliday::RoshHashanah, (9, 19)),
+        (HebrewHoliday::Sukkot, (10, 3)),
+        (HebrewHoliday::Hanukkah, (12, 11)),
+        (HebrewHoliday::Purim, (3, 10)),
+        (HebrewHoliday::Passover, (4, 9)),
+        (HebrewHoliday::Shavuot, (5, 29)),
+        (HebrewHoliday::TishaBAv, (7, 30)),
+    ];
+    for (holiday, (month, day)) in &holidays {
+        assert_eq!(holiday.date(2020), Some(NaiveDate::from_ymd_opt(2020, *month, *day).unwrap()));
+    }
+    // Purim in a leap year, and Tisha B'Av postponed from the Sabbath
+    assert_eq!(
+        HebrewHoliday::Purim.date(2022),
+        Some(NaiveDate::from_ymd_opt(2022, 3, 17).unwrap())
+    );
+    assert_eq!(
+        HebrewHoliday::TishaBAv.date(2022),
+        Some(NaiveDate::from_ymd_opt(2022, 8, 7).unwrap())
+    );
+
+    let tishri: Molad = molad(5781, TISHRI).unwrap();
+    assert_eq!(tishri.weekday, Weekday::Thursday);
+    assert_eq!((tishri.hour, tishri.minute, tishri.halakim), (14, 38, 17));
+
+    // The molad is within a day of the true new moon
+    for month in 1..=12 {
+        let molad = molad(5781, month).unwrap();
+        let new_moon = crate::calendar::new_moon_at_or_after(
+            crate::conv::fixed_from_chrono(molad.moment) - 1.0,
+            &crate::delta_t::EspenakMeeus,
+        );
+        assert!((crate::conv::fixed_from_chrono(molad.moment) - new_moon).abs() < 1.0);
+    }
+}
@@ -0,0 +1,395 @@
+// Copyright (c) 2018 Edward M. Reingold and Nachum Dershowitz
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+//
+// Functions from chapters 7, "The Islamic and Bahá'í Calendars", and 18, "Astronomical Lunar
+// Calendars", of:
+//
+//     E. M. Reingold and N. Dershowitz, Calendrical Calculations: The Ultimate Edition.
+//         Cambridge University Press, 2018. doi:10.1017/9781107415058
+//
+// See `calendar.rs` for the representation of dates and moments.
+
+#![allow(clippy::unreadable_literal)]
+
+use crate::calendar::{
+    odeh_visible_crescent, phasis_on_or_before, shaukat_visible_crescent, yallop_visible_crescent,
+    MEAN_SYNODIC_MONTH,
+};
+use crate::delta_t::{DeltaT, EspenakMeeus};
+use crate::location::Location;
+use crate::moment::{CivilDate, Day, FixedDate};
+use crate::util::{floor, i32, nonneg, round};
+use core::convert::TryFrom;
+
+// fixed_from_julian(622, 7, 16)
+const ISLAMIC_EPOCH: i32 = 227015;
+
+const RAMADAN: u32 = 9;
+const SHAWWAL: u32 = 10;
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn fixed_from_islamic(year: i32, month: u32, day: u32) -> i32 {
+    let (year, month, day) = (i64::from(year), i64::from(month), i64::from(day));
+    (day + 29 * (month - 1)
+        + (6 * month - 1).div_euclid(11)
+        + (year - 1) * 354
+        + (3 + 11 * year).div_euclid(30)
+        + i64::from(ISLAMIC_EPOCH)
+        - 1) as i32
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn islamic_from_fixed(date: i32) -> (i32, u32, u32) {
+    let year = ((30 * i64::from(date - ISLAMIC_EPOCH) + 10646).div_euclid(10631)) as i32;
+    let prior_days = date - fixed_from_islamic(year, 1, 1);
+    let month = nonneg((11 * prior_days + 330).div_euclid(325));
+    let day = nonneg(date - fixed_from_islamic(year, month, 1) + 1);
+    (year, month, day)
+}
+
+fn fixed_from_observational_islamic<F>(
+    year: i32,
+    month: u32,
+    day: u32,
+    visible: F,
+    dt: &dyn DeltaT,
+) -> Option<i32>
+where
+    F: Fn(i32) -> bool,
+{
+    let midmonth = ISLAMIC_EPOCH
+        + i32(floor((f64::from(year - 1) * 12.0 + f64::from(month) - 0.5) * MEAN_SYNODIC_MONTH));
+    Some(phasis_on_or_before(midmonth, visible, dt)? + i32::try_from(day).ok()? - 1)
+}
+
+fn observational_islamic_from_fixed<F>(
+    date: i32,
+    visible: F,
+    dt: &dyn DeltaT,
+) -> Option<(i32, u32, u32)>
+where
+    F: Fn(i32) -> bool,
+{
+    let crescent = phasis_on_or_before(date, visible, dt)?;
+    let elapsed_months = i32(round(f64::from(crescent - ISLAMIC_EPOCH) / MEAN_SYNODIC_MONTH));
+    let year = elapsed_months.div_euclid(12) + 1;
+    let month = nonneg(elapsed_months.rem_euclid(12) + 1);
+    let day = nonneg(date - crescent + 1);
+    Some((year, month, day))
+}
+
+// =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=
+
+/// A criterion for predicting whether the crescent moon can be seen with the naked eye after
+/// sunset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrescentCriterion {
+    /// The criterion of K. Shaukat, as used by <i>Calendrical Calculations</i>: at dusk (when
+    /// the Sun is 4.5&deg; below the horizon), the Moon must be at least 10.6&deg; from the Sun
+    /// and more than 4.1&deg; above the horizon.
+    Shaukat,
+    /// The criterion of B. D. Yallop (HM Nautical Almanac Office Technical Note 69), at Bruin's
+    /// best time of observation. The crescent is considered visible in Yallop's categories A and
+    /// B (<i>q</i>&nbsp;&gt;&nbsp;&minus;0.014).
+    Yallop,
+    /// The criterion of M. S. Odeh (<i>Experimental Astronomy</i> 18, 2004), at Bruin's best time
+    /// of observation. The crescent is considered visible in Odeh's zone A
+    /// (<i>V</i>&nbsp;&ge;&nbsp;5.65).
+    Odeh,
+}
+
+/// A form of the Islamic calendar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IslamicCalendar {
+    /// The tabular (arithmetic) calendar, in which months alternate between 30 and 29 days and
+    /// eleven of every thirty years are leap years.
+    Arithmetic,
+    /// The observational calendar, in which each month begins on the day after the evening the
+    /// crescent moon is predicted to first be visible at a location.
+    ///
+    /// <i>Calendrical Calculations</i> uses Cairo (30.1&deg;&nbsp;N, 31.3&deg;&nbsp;E, 200 m) and
+    /// the Shaukat criterion.
+    Observational {
+        /// The location of the observer.
+        location: Location,
+        /// The criterion for predicting visibility of the crescent.
+        criterion: CrescentCriterion,
+    },
+}
+
+impl CrescentCriterion {
+    pub(crate) fn visible(self, date: i32, location: Location, dt: &dyn DeltaT) -> bool {
+        match self {
+            CrescentCriterion::Shaukat => shaukat_visible_crescent(date, location, dt),
+            CrescentCriterion::Yallop => yallop_visible_crescent(date, location, dt),
+            CrescentCriterion::Odeh => odeh_visible_crescent(date, location, dt),
+        }
+    }
+}
+
+/// Returns true if the crescent moon is predicted to be visible to the naked eye at a location on
+/// the evening of `date`.
+///
+/// ```
+/// use esbat::{CrescentCriterion, FixedDate, Location};
+///
+/// let cairo = Location::new(30.1, 31.3, 200.0);
+/// let date = |d| FixedDate::from_gregorian(2020, 4, d).unwrap();
+/// assert!(!esbat::is_crescent_visible(date(23), cairo, CrescentCriterion::Yallop));
+/// assert!(esbat::is_crescent_visible(date(24), cairo, CrescentCriterion::Yallop));
+/// ```
+pub fn is_crescent_visible<D: Day>(
+    date: D,
+    location: Location,
+    criterion: CrescentCriterion,
+) -> bool {
+    criterion.visible(date.fixed_date().rd(), location, &EspenakMeeus)
+}
+
+/// A date in the Islamic calendar.
+///
+/// Months are numbered from 1 (Muharram) to 12 (Dhu al-Hijjah); Ramadan is month 9. Years are
+/// counted from the Hijra (<i>anno Hegirae</i>).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IslamicDate {
+    /// The year.
+    pub year: i32,
+    /// The month, from 1 to 12.
+    pub month: u32,
+    /// The day of the month, from 1 to 30.
+    pub day: u32,
+}
+
+impl IslamicDate {
+    /// Converts a date in the proleptic Gregorian calendar to a form of the Islamic calendar.
+    ///
+    /// The Islamic day begins at sunset; this returns the Islamic date that begins at sunset on the
+    /// evening before `date`.
+    ///
+    /// Returns `None` for the observational calendar if the crescent is not predicted to be visible
+    /// at the location for two months, such as near the poles.
+    ///
+    /// ```
+    /// use esbat::{FixedDate, IslamicCalendar, IslamicDate};
+    ///
+    /// let date = FixedDate::from_gregorian(2020, 4, 24).unwrap();
+    /// let date = IslamicDate::from_gregorian(date, IslamicCalendar::Arithmetic);
+    /// assert_eq!(date, Some(IslamicDate { year: 1441, month: 9, day: 1 }));
+    /// ```
+    pub fn from_gregorian<D: Day>(date: D, calendar: IslamicCalendar) -> Option<IslamicDate> {
+        IslamicDate::from_gregorian_with(date, calendar, &EspenakMeeus)
+    }
+
+    pub(crate) fn from_gregorian_with<D: Day>(
+        date: D,
+        calendar: IslamicCalendar,
+        dt: &dyn DeltaT,
+    ) -> Option<IslamicDate> {
+        let date = date.fixed_date().rd();
+        let (year, month, day) = match calendar {
+            IslamicCalendar::Arithmetic => islamic_from_fixed(date),
+            IslamicCalendar::Observational { location, criterion } => {
+                observational_islamic_from_fixed(date, |d| criterion.visible(d, location, dt), dt)?
+            }
+        };
+        Some(IslamicDate { year, month, day })
+    }
+
+    /// Converts the date in a form of the Islamic calendar to the proleptic Gregorian calendar.
+    ///
+    /// Returns `None` if the date cannot be represented by `D`, or for the observational calendar if
+    /// the crescent is not predicted to be visible at the location for two months.
+    ///
+    /// ```
+    /// use esbat::{FixedDate, IslamicCalendar, IslamicDate};
+    ///
+    /// let date = IslamicDate { year: 1441, month: 10, day: 1 };
+    /// assert_eq!(
+    ///     date.to_gregorian(IslamicCalendar::Arithmetic),
+    ///     Some(FixedDate::from_gregorian(2020, 5, 24).unwrap())
+    /// );
+    /// ```
+    pub fn to_gregorian<D: CivilDate>(self, calendar: IslamicCalendar) -> Option<D> {
+        self.to_gregorian_with(calendar, &EspenakMeeus)
+    }
+
+    pub(crate) fn to_gregorian_with<D: CivilDate>(
+        self,
+        calendar: IslamicCalendar,
+        dt: &dyn DeltaT,
+    ) -> Option<D> {
+        D::from_fixed(FixedDate::from_rd(match calendar {
+            IslamicCalendar::Arithmetic => fixed_from_islamic(self.year, self.month, self.day),
+            IslamicCalendar::Observational { location, criterion } => {
+                fixed_from_observational_islamic(
+                    self.year,
+                    self.month,
+                    self.day,
+                    |d| criterion.visible(d, location, dt),
+                    dt,
+                )?
+            }
+        }))
+    }
+}
+
+/// Predicts the first day of Ramadan and the first day of Shawwal (Eid al-Fitr) in an Islamic
+/// year.
+///
+/// Returns `None` if the dates cannot be represented by `D`, or for the observational calendar if
+/// the crescent is not predicted to be visible at the location for two months.
+///
+/// ```
+/// use esbat::{CrescentCriterion, FixedDate, IslamicCalendar, Location};
+///
+/// let calendar = IslamicCalendar::Observational {
+///     location: Location::new(30.1, 31.3, 200.0),
+///     criterion: CrescentCriterion::Shaukat,
+/// };
+/// let start = FixedDate::from_gregorian(2020, 4, 25).unwrap();
+/// let end = FixedDate::from_gregorian(2020, 5, 24).unwrap();
+/// assert_eq!(esbat::ramadan(1441, calendar), Some((start, end)));
+/// ```
+pub fn ramadan<D: CivilDate>(year: i32, calendar: IslamicCalendar) -> Option<(D, D)> {
+    ramadan_with(year, calendar, &EspenakMeeus)
+}
+
+pub(crate) fn ramadan_with<D: CivilDate>(
+    year: i32,
+    calendar: IslamicCalendar,
+    dt: &dyn DeltaT,
+) -> Option<(D, D)> {
+    let start = IslamicDate { year, month: RAMADAN, day: 1 }.to_gregorian_with(calendar, dt)?;
+    let end = IslamicDate { year, month: SHAWWAL, day: 1 }.to_gregorian_with(calendar, dt)?;
+    Some((start, end))
+}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_islamic() {
+    use chrono::NaiveDate;
+
+    // Calendrical Calculations, appendix C
+    let sample = [
+        (-1245, 12, 9),
+        (-813, 2, 23),
+        (-568, 4, 1),
+        (-501, 4, 6),
+        (-157, 10, 17),
+        (-47, 6, 3),
+        (75, 7, 13),
+        (403, 10, 5),
+        (489, 5, 22),
+        (586, 2, 7),
+        (637, 8, 7),
+        (687, 2, 20),
+        (697, 7, 7),
+        (793, 7, 1),
+        (839, 7, 6),
+        (897, 6, 1),
+        (960, 9, 30),
+        (967, 5, 27),
+        (1058, 5, 18),
+        (1091, 6, 2),
+        (1128, 8, 4),
+        (1182, 2, 3),
+        (1234, 10, 10),
+        (1255, 1, 11),
+        (1321, 1, 21),
+        (1348, 3, 19),
+        (1360, 9, 8),
+        (1362, 4, 13),
+        (1362, 10, 7),
+        (1412, 9, 13),
+        (1416, 10, 5),
+        (1460, 10, 12),
+        (1518, 3, 5),
+    ];
+    for ((rd, _, _, _, _, _), (year, month, day)) in crate::data::TEST_DATA.iter().zip(&sample) {
+        let rd = i32(*rd);
+        assert_eq!(islamic_from_fixed(rd), (*year, *month, *day));
+        assert_eq!(fixed_from_islamic(*year, *month, *day), rd);
+    }
+
+    let sample = [
+        (-1245, 12, 11),
+        (-813, 2, 25),
+        (-568, 4, 2),
+        (-501, 4, 7),
+        (-157, 10, 18),
+        (-47, 6, 3),
+        (75, 7, 13),
+        (403, 10, 5),
+        (489, 5, 22),
+        (586, 2, 7),
+        (637, 8, 7),
+        (687, 2, 21),
+        (697, 7, 7),
+        (793, 6, 30),
+        (839, 7, 6),
+        (897, 6, 2),
+        (960, 9, 30),
+        (967, 5, 27),
+        (1058, 5, 18),
+        (1091, 6, 3),
+        (1128, 8, 4),
+        (1182, 2, 4),
+        (1234, 10, 10),
+        (1255, 1, 11),
+        (1321, 1, 20),
+        (1348, 3, 19),
+        (1360, 9, 7),
+        (1362, 4, 14),
+        (1362, 10, 7),
+        (1412, 9, 12),
+        (1416, 10, 5),
+        (1460, 10, 12),
+        (1518, 3, 5),
+    ];
+    let cairo = Location::new(30.1, 31.3, 200.0);
+    let visible = |d| shaukat_visible_crescent(d, cairo, &EspenakMeeus);
+    for ((rd, _, _, _, _, _), (year, month, day)) in crate::data::TEST_DATA.iter().zip(&sample) {
+        let rd = i32(*rd);
+        let dt = &EspenakMeeus;
+        assert_eq!(observational_islamic_from_fixed(rd, visible, dt), Some((*year, *month, *day)));
+        assert_eq!(fixed_from_observational_islamic(*year, *month, *day, visible, dt), Some(rd));
+    }
+
+    let ymd = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    for criterion in
+        &[CrescentCriterion::Shaukat, CrescentCriterion::Yallop, CrescentCriterion::Odeh]
+    {
+        let calendar = IslamicCalendar::Observational { location: cairo, criterion: *criterion };
+        assert_eq!(ramadan(1442, calendar), Some((ymd(2021, 4, 14), ymd(2021, 5, 14))));
+        assert_eq!(ramadan(1444, calendar), Some((ymd(2023, 3, 23), ymd(2023, 4, 22))));
+    }
+    assert_eq!(
+        ramadan(1442, IslamicCalendar::Arithmetic),
+        Some((ymd(2021, 4, 13), ymd(2021, 5, 13)))
+    );
+}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_islamic_polar() {
+    use chrono::NaiveDate;
+
+    // The Sun does not set near the North Pole in summer, so no crescent is seen
+    let calendar = IslamicCalendar::Observational {
+        location: Location::new(89.0, 0.0, 0.0),
+        criterion: CrescentCriterion::Shaukat,
+    };
+    assert_eq!(
+        IslamicDate::from_gregorian(NaiveDate::from_ymd_opt(2020, 6, 21).unwrap(), calendar),
+        None
+    );
+    assert_eq!(
+        IslamicDate { year: 1441, month: 11, day: 1 }.to_gregorian::<NaiveDate>(calendar),
+        None
+    );
+}
@@ -6,140 +6,122 @@
 // or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
 
 use crate::calendar::{lunar_phase, lunar_phase_at_or_after, lunar_phase_at_or_before};
-use crate::conv::{chrono_from_fixed, fixed_from_chrono};
+use crate::delta_t::{DeltaT, EspenakMeeus};
+use crate::moment::{CivilDate, Day, FixedDate, LocalDate, Moment, Timestamp};
 use crate::phase::PrincipalPhase;
-use chrono::{Date, DateTime, Duration, Utc};
+use crate::util::{ceil, clamp_angle, floor, rem_euclid};
 use core::iter::FusedIterator;
+use core::marker::PhantomData;
 use core::ops::{Bound, RangeBounds};
 
-fn min_time() -> DateTime<Utc> {
-    chrono::MIN_DATE.and_hms(0, 0, 0)
-}
-
-fn max_time() -> DateTime<Utc> {
-    chrono::MAX_DATE.and_hms_nano(23, 59, 59, 999_999_999)
+pub(crate) fn add_day(t: f64, positive: bool) -> f64 {
+    if positive {
+        t + 1.0
+    } else {
+        t - 1.0
+    }
 }
 
-fn add_day(t: DateTime<Utc>, positive: bool) -> DateTime<Utc> {
-    t.checked_add_signed(Duration::days(if positive { 1 } else { -1 })).unwrap_or_else(|| {
-        if positive {
-            max_time()
-        } else {
-            min_time()
-        }
-    })
+fn add_days(t: f64, days: f64, positive: bool) -> f64 {
+    if positive {
+        t + days
+    } else {
+        t - days
+    }
 }
 
-fn handle_bound<T: Copy, F>(bound: Bound<&T>, default: F) -> (T, bool)
+pub(crate) fn handle_bound<T: Clone, F>(bound: Bound<&T>, default: F) -> (T, bool)
 where
     F: Fn() -> T,
 {
     match bound {
-        Bound::Included(t) => (*t, false),
-        Bound::Excluded(t) => (*t, true),
+        Bound::Included(t) => (t.clone(), false),
+        Bound::Excluded(t) => (t.clone(), true),
         Bound::Unbounded => (default(), false),
     }
 }
 
-/// Returns an iterator of principal phases and their moments.
-///
-/// ```
-/// use chrono::{TimeZone, Utc};
-/// use esbat::PrincipalPhase;
-///
-/// let start = Utc.ymd(2020, 10, 1).and_hms(0, 0, 0);
-/// let end = Utc.ymd(2020, 11, 1).and_hms(0, 0, 0);
-/// let mut iter = esbat::lunar_phase_iter(start..end);
-///
-/// assert_eq!(iter.next().unwrap().0, PrincipalPhase::FullMoon);
-/// assert_eq!(iter.next().unwrap().0, PrincipalPhase::LastQuarter);
-/// assert_eq!(iter.next().unwrap().0, PrincipalPhase::NewMoon);
-/// assert_eq!(iter.next().unwrap().0, PrincipalPhase::FirstQuarter);
-/// assert_eq!(iter.next().unwrap().0, PrincipalPhase::FullMoon);
-/// assert!(iter.next().is_none());
-/// ```
-pub fn lunar_phase_iter<B>(range: B) -> Iter
+// The moments at the start and end of a range, and whether each is excluded
+pub(crate) fn moment_bounds<T, B>(range: &B) -> ((f64, bool), (f64, bool))
 where
-    B: RangeBounds<DateTime<Utc>>,
+    T: Timestamp,
+    B: RangeBounds<T>,
 {
-    let (start, start_excl) = handle_bound(range.start_bound(), min_time);
-    let (end, end_excl) = handle_bound(range.end_bound(), max_time);
-    Iter::new(start, start_excl, end, end_excl)
+    let (start, start_excl) = handle_bound(range.start_bound(), T::min_value);
+    let (end, end_excl) = handle_bound(range.end_bound(), T::max_value);
+    ((start.to_moment().rd(), start_excl), (end.to_moment().rd(), end_excl))
 }
 
-/// Principal phase iterator.
-///
-/// This struct is created by [`lunar_phase_iter`].
+// An angle that increases steadily over time (such as the lunar phase or solar longitude), the
+// functions for finding when it next or last reaches a given value, the interval between the
+// values of interest, and how many days past each value found to resume searching (which must be
+// less than the time the angle takes to advance by `step`).
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Angle {
+    pub(crate) value: fn(f64, &dyn DeltaT) -> f64,
+    pub(crate) at_or_after: fn(f64, f64, &dyn DeltaT) -> f64,
+    pub(crate) at_or_before: fn(f64, f64, &dyn DeltaT) -> f64,
+    pub(crate) step: f64,
+    pub(crate) skip: f64,
+}
+
+// Iterator of the moments an `Angle` reaches each multiple of its step, and the multiple reached,
+// using the ΔT model `M`.
 #[derive(Debug, Clone)]
-pub struct Iter {
-    bound: Option<(DateTime<Utc>, DateTime<Utc>)>,
+pub(crate) struct AngleIter<T, M> {
+    angle: Angle,
+    bound: Option<(f64, f64)>,
     positive: bool,
+    model: M,
+    time: PhantomData<T>,
 }
 
-impl Iter {
-    fn new(
-        mut start: DateTime<Utc>,
-        start_excl: bool,
-        mut end: DateTime<Utc>,
-        end_excl: bool,
-    ) -> Iter {
-        fn close_to_phase(t: DateTime<Utc>) -> bool {
-            let x = lunar_phase(fixed_from_chrono(t)).rem_euclid(90.0);
-            x < 0.00001 || 89.99999 < x
-        }
+impl<T: Timestamp, M: DeltaT> AngleIter<T, M> {
+    pub(crate) fn new(
+        angle: Angle,
+        (mut start, start_excl): (f64, bool),
+        (mut end, end_excl): (f64, bool),
+        model: M,
+    ) -> AngleIter<T, M> {
+        let close_to_step = |t: f64| {
+            let x = rem_euclid((angle.value)(t, &model), angle.step);
+            x < 0.00001 || angle.step - 0.00001 < x
+        };
 
         let positive = start <= end;
-        if start_excl && close_to_phase(start) {
-            start = add_day(start, positive);
+        if start_excl && close_to_step(start) {
+            start = add_days(start, angle.skip, positive);
         }
-        if end_excl && close_to_phase(end) {
-            end = add_day(end, !positive);
+        if end_excl && close_to_step(end) {
+            end = add_days(end, angle.skip, !positive);
         }
-        Iter { bound: Some((start, end)), positive }
+        AngleIter { angle, bound: Some((start, end)), positive, model, time: PhantomData }
     }
 }
 
-impl Iterator for Iter {
-    type Item = (PrincipalPhase, DateTime<Utc>);
+impl<T: Timestamp, M: DeltaT> Iterator for AngleIter<T, M> {
+    type Item = (f64, T);
 
-    fn next(&mut self) -> Option<(PrincipalPhase, DateTime<Utc>)> {
+    fn next(&mut self) -> Option<(f64, T)> {
         let (start, end) = self.bound?;
-        let start = fixed_from_chrono(start);
-
-        let phase = lunar_phase(start);
-        let phase = if self.positive {
-            if phase <= 90.0 {
-                PrincipalPhase::FirstQuarter
-            } else if phase <= 180.0 {
-                PrincipalPhase::FullMoon
-            } else if phase <= 270.0 {
-                PrincipalPhase::LastQuarter
-            } else {
-                PrincipalPhase::NewMoon
-            }
-        } else if phase >= 270.0 {
-            PrincipalPhase::LastQuarter
-        } else if phase >= 180.0 {
-            PrincipalPhase::FullMoon
-        } else if phase >= 90.0 {
-            PrincipalPhase::FirstQuarter
-        } else {
-            PrincipalPhase::NewMoon
-        };
 
-        let next_rd = if self.positive {
-            lunar_phase_at_or_after(phase.as_angle(), start)
+        let step = self.angle.step;
+        let current = (self.angle.value)(start, &self.model) / step;
+        let (target, next) = if self.positive {
+            let target = clamp_angle(ceil(current).max(1.0) * step);
+            (target, (self.angle.at_or_after)(target, start, &self.model))
         } else {
-            lunar_phase_at_or_before(phase.as_angle(), start)
+            let target = floor(current) * step;
+            (target, (self.angle.at_or_before)(target, start, &self.model))
         };
 
-        if let Some(next) = chrono_from_fixed(next_rd) {
+        if let Some(t) = T::from_moment(Moment::from_rd(next)) {
             if self.positive && next <= end {
-                self.bound = Some((add_day(next, true), end));
-                return Some((phase, next));
+                self.bound = Some((add_days(next, self.angle.skip, true), end));
+                return Some((target, t));
             } else if !self.positive && next >= end {
-                self.bound = Some((add_day(next, false), end));
-                return Some((phase, next));
+                self.bound = Some((add_days(next, self.angle.skip, false), end));
+                return Some((target, t));
             }
         }
 
@@ -148,117 +130,326 @@ impl Iterator for Iter {
     }
 }
 
-impl FusedIterator for Iter {}
+/// Returns an iterator of principal phases and their moments.
+///
+/// ```
+/// use esbat::{FixedDate, PrincipalPhase};
+///
+/// let start = FixedDate::from_gregorian(2020, 10, 1).unwrap().moment();
+/// let end = FixedDate::from_gregorian(2020, 11, 1).unwrap().moment();
+/// let mut iter = esbat::lunar_phase_iter(start..end);
+///
+/// assert_eq!(iter.next().unwrap().0, PrincipalPhase::FullMoon);
+/// assert_eq!(iter.next().unwrap().0, PrincipalPhase::LastQuarter);
+/// assert_eq!(iter.next().unwrap().0, PrincipalPhase::NewMoon);
+/// assert_eq!(iter.next().unwrap().0, PrincipalPhase::FirstQuarter);
+/// assert_eq!(iter.next().unwrap().0, PrincipalPhase::FullMoon);
+/// assert!(iter.next().is_none());
+/// ```
+pub fn lunar_phase_iter<T, B>(range: B) -> Iter<T>
+where
+    T: Timestamp,
+    B: RangeBounds<T>,
+{
+    let (start, end) = moment_bounds(&range);
+    Iter::new(start, end, EspenakMeeus)
+}
+
+/// Principal phase iterator.
+///
+/// This struct is created by [`lunar_phase_iter`] or
+/// [`Ephemeris::lunar_phase_iter`](crate::Ephemeris::lunar_phase_iter), which determines the ΔT
+/// model `M`.
+#[derive(Debug, Clone)]
+pub struct Iter<T = Moment, M = EspenakMeeus> {
+    inner: AngleIter<T, M>,
+}
+
+impl<T: Timestamp, M: DeltaT> Iter<T, M> {
+    pub(crate) fn new(start: (f64, bool), end: (f64, bool), model: M) -> Iter<T, M> {
+        Iter {
+            inner: AngleIter::new(
+                Angle {
+                    value: lunar_phase,
+                    at_or_after: lunar_phase_at_or_after,
+                    at_or_before: lunar_phase_at_or_before,
+                    step: 90.0,
+                    skip: 1.0,
+                },
+                start,
+                end,
+                model,
+            ),
+        }
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> Iterator for Iter<T, M> {
+    type Item = (PrincipalPhase, T);
+
+    fn next(&mut self) -> Option<(PrincipalPhase, T)> {
+        let (angle, next) = self.inner.next()?;
+        Some((PrincipalPhase::from_angle(angle), next))
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> FusedIterator for Iter<T, M> {}
 
 #[cfg(test)]
 #[test]
 fn test_iter_rev() {
-    use chrono::TimeZone;
-
-    let start = Utc.ymd(2020, 11, 1).and_hms(0, 0, 0);
-    let end = Utc.ymd(2020, 10, 1).and_hms(0, 0, 0);
-    let mut iter = lunar_phase_iter(start..end);
+    use crate::moment::moment_from_parts;
+    use crate::util::assert_events;
 
-    assert_eq!(iter.next().unwrap().0, PrincipalPhase::FullMoon);
-    assert_eq!(iter.next().unwrap().0, PrincipalPhase::FirstQuarter);
-    assert_eq!(iter.next().unwrap().0, PrincipalPhase::NewMoon);
-    assert_eq!(iter.next().unwrap().0, PrincipalPhase::LastQuarter);
-    assert_eq!(iter.next().unwrap().0, PrincipalPhase::FullMoon);
-    assert!(iter.next().is_none());
+    // Phase times from the US Naval Observatory
+    let at = |m, d, h, min| moment_from_parts((2020, m, d), (h, min, 0, 0));
+    let expected = [
+        (PrincipalPhase::FullMoon, at(10, 31, 14, 49)),
+        (PrincipalPhase::FirstQuarter, at(10, 23, 13, 23)),
+        (PrincipalPhase::NewMoon, at(10, 16, 19, 31)),
+        (PrincipalPhase::LastQuarter, at(10, 10, 0, 39)),
+        (PrincipalPhase::FullMoon, at(10, 1, 21, 5)),
+    ];
+    assert_events(lunar_phase_iter(at(11, 1, 0, 0)..at(10, 1, 0, 0)), &expected, 2);
 }
 
 /// Returns an iterator of principal phases and the days they fall on.
 ///
 /// ```
-/// use chrono::{TimeZone, Utc};
+/// use esbat::{FixedDate, PrincipalPhase};
+///
+/// let date = |m, d| FixedDate::from_gregorian(2020, m, d).unwrap();
+/// let mut iter = esbat::daily_lunar_phase_iter(date(10, 1)..date(11, 1));
+///
+/// assert_eq!(iter.next().unwrap(), (PrincipalPhase::FullMoon, date(10, 1)));
+/// assert_eq!(iter.next().unwrap(), (PrincipalPhase::LastQuarter, date(10, 10)));
+/// assert_eq!(iter.next().unwrap(), (PrincipalPhase::NewMoon, date(10, 16)));
+/// assert_eq!(iter.next().unwrap(), (PrincipalPhase::FirstQuarter, date(10, 23)));
+/// assert_eq!(iter.next().unwrap(), (PrincipalPhase::FullMoon, date(10, 31)));
+/// assert!(iter.next().is_none());
+/// ```
+pub fn daily_lunar_phase_iter<D, B>(range: B) -> DailyIter<D>
+where
+    D: CivilDate,
+    B: RangeBounds<D>,
+{
+    let (start, end, zone) = day_bounds(&range);
+    DailyIter::new(start, end, zone, EspenakMeeus)
+}
+
+// The moments at which a day begins and ends, and whether it is excluded from a range
+type DayBound = ((f64, f64), bool);
+
+// The first and last days of a range, and a day in the time zone of the range
+pub(crate) fn day_bounds<D, B>(range: &B) -> (DayBound, DayBound, Option<D>)
+where
+    D: CivilDate,
+    B: RangeBounds<D>,
+{
+    let bounds = |date: &D| {
+        let (start, end) = date.bounds();
+        (start.rd(), end.rd())
+    };
+
+    let (start, start_excl) = handle_bound(range.start_bound(), D::min_value);
+    let (end, end_excl) = handle_bound(range.end_bound(), D::max_value);
+    ((bounds(&start), start_excl), (bounds(&end), end_excl), Some(start))
+}
+
+/// Returns an iterator of principal phases and the days they fall on in the time zone `zone`.
+///
+/// The range is of dates in `zone`, each running from local midnight to local midnight, so days on
+/// which daylight saving time begins or ends are 23 or 25 hours long. Each principal phase is
+/// returned as its moment in `zone`, and falls on that moment's local date.
+///
+/// This works with any [`LocalDate`]: Chrono's `NaiveDate` in a Chrono `TimeZone`, the `time`
+/// crate's `Date` at a `UtcOffset`, or Jiff's civil `Date` in a Jiff `TimeZone`.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{FixedOffset, NaiveDate};
 /// use esbat::PrincipalPhase;
 ///
-/// let start = Utc.ymd(2020, 10, 1);
-/// let end = Utc.ymd(2020, 11, 1);
-/// let mut iter = esbat::daily_lunar_phase_iter(start..end);
+/// let date = |m, d| NaiveDate::from_ymd_opt(2020, m, d).unwrap();
+/// let tz = FixedOffset::east_opt(10 * 3600).unwrap();
+/// let mut iter = esbat::daily_lunar_phase_iter_in(date(10, 25)..date(11, 5), tz);
 ///
-/// assert_eq!(iter.next().unwrap(), (PrincipalPhase::FullMoon, Utc.ymd(2020, 10, 1)));
-/// assert_eq!(iter.next().unwrap(), (PrincipalPhase::LastQuarter, Utc.ymd(2020, 10, 10)));
-/// assert_eq!(iter.next().unwrap(), (PrincipalPhase::NewMoon, Utc.ymd(2020, 10, 16)));
-/// assert_eq!(iter.next().unwrap(), (PrincipalPhase::FirstQuarter, Utc.ymd(2020, 10, 23)));
-/// assert_eq!(iter.next().unwrap(), (PrincipalPhase::FullMoon, Utc.ymd(2020, 10, 31)));
+/// // The full moon at 14:49 UTC on October 31 falls on November 1 at UTC+10
+/// let (phase, t) = iter.next().unwrap();
+/// assert_eq!(phase, PrincipalPhase::FullMoon);
+/// assert_eq!(t.date_naive(), date(11, 1));
 /// assert!(iter.next().is_none());
+/// # }
 /// ```
-pub fn daily_lunar_phase_iter<B>(range: B) -> DailyIter
+pub fn daily_lunar_phase_iter_in<D, Z, B>(range: B, zone: Z) -> DailyIter<D::Local>
 where
-    B: RangeBounds<Date<Utc>>,
+    D: LocalDate<Z>,
+    B: RangeBounds<D>,
 {
-    fn fix(t: Date<Utc>, down: bool) -> DateTime<Utc> {
-        if down {
-            t.and_hms(0, 0, 0)
-        } else {
-            t.and_hms_nano(23, 59, 59, 999_999_999)
-        }
-    }
+    let (start, end, template) = local_day_bounds(&range, &zone);
+    DailyIter::new(start, end, template, EspenakMeeus)
+}
+
+// The first and last days of a range of dates in `zone`, and a day in `zone`
+pub(crate) fn local_day_bounds<D, Z, B>(
+    range: &B,
+    zone: &Z,
+) -> (DayBound, DayBound, Option<D::Local>)
+where
+    D: LocalDate<Z>,
+    B: RangeBounds<D>,
+{
+    let bounds = |date: &D| {
+        let (start, end) = date.local_bounds(zone);
+        (start.rd(), end.rd())
+    };
 
-    let (start, start_excl) = handle_bound(range.start_bound(), || chrono::MIN_DATE);
-    let (end, end_excl) = handle_bound(range.end_bound(), || chrono::MAX_DATE);
-    let positive = start <= end;
-    DailyIter { inner: Iter::new(fix(start, positive), start_excl, fix(end, !positive), end_excl) }
+    let (start, start_excl) = handle_bound(range.start_bound(), D::min_value);
+    let (end, end_excl) = handle_bound(range.end_bound(), D::max_value);
+    let (start, end) = (bounds(&start), bounds(&end));
+    // Days are returned as moments in `zone`; one end of the range is always representable
+    let template = D::local_time(zone, Moment::from_rd(start.0))
+        .or_else(|| D::local_time(zone, Moment::from_rd(end.0)));
+    ((start, start_excl), (end, end_excl), template)
 }
 
 /// Principal phase iterator by day.
 ///
-/// This struct is created by [`daily_lunar_phase_iter`].
+/// This struct is created by [`daily_lunar_phase_iter`] or [`daily_lunar_phase_iter_in`], or the
+/// methods of the same names of [`Ephemeris`](crate::Ephemeris), which determine the ΔT model `M`.
 #[derive(Debug, Clone)]
-pub struct DailyIter {
-    inner: Iter,
+pub struct DailyIter<D = FixedDate, M = EspenakMeeus> {
+    inner: Iter<Moment, M>,
+    // Any day in the time zone of the days returned, if there is one
+    zone: Option<D>,
 }
 
-impl Iterator for DailyIter {
-    type Item = (PrincipalPhase, Date<Utc>);
+impl<D: Day, M: DeltaT> DailyIter<D, M> {
+    // `start` and `end` are the moments at which the first and last days of the range begin and
+    // end, and whether each day is excluded.
+    pub(crate) fn new(
+        start: DayBound,
+        end: DayBound,
+        zone: Option<D>,
+        model: M,
+    ) -> DailyIter<D, M> {
+        let (((start_begin, start_end), start_excl), ((end_begin, end_end), end_excl)) =
+            (start, end);
+        // Iterate from the first moment of the first day to the last moment of the last day, in
+        // whichever direction the range runs
+        let last_moment = |end: f64| end - 1.0 / 86_400_000.0;
+        let (start, end) = if start_begin <= end_begin {
+            (start_begin, last_moment(end_end))
+        } else {
+            (last_moment(start_end), end_begin)
+        };
+        DailyIter { inner: Iter::new((start, start_excl), (end, end_excl), model), zone }
+    }
+}
+
+impl<D: Day, M: DeltaT> Iterator for DailyIter<D, M> {
+    type Item = (PrincipalPhase, D);
 
-    fn next(&mut self) -> Option<(PrincipalPhase, Date<Utc>)> {
-        let next = self.inner.next()?;
-        dbg!(&self.inner);
-        Some((next.0, next.1.date()))
+    fn next(&mut self) -> Option<(PrincipalPhase, D)> {
+        let zone = self.zone.as_ref()?;
+        let (phase, t) = self.inner.next()?;
+        Some((phase, zone.day_containing(t)?))
     }
 }
 
-impl FusedIterator for DailyIter {}
+impl<D: Day, M: DeltaT> FusedIterator for DailyIter<D, M> {}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_moment_iter() {
+    use crate::moment::Time;
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    let start = Utc.with_ymd_and_hms(2020, 10, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2020, 11, 1, 0, 0, 0).unwrap();
+    let moments = lunar_phase_iter(start.to_moment()..end.to_moment()).collect::<Vec<_>>();
+    let times = lunar_phase_iter(start..end).collect::<Vec<_>>();
+    assert_eq!(moments.len(), times.len());
+    for ((phase, moment), (expected_phase, t)) in moments.into_iter().zip(times) {
+        assert_eq!(phase, expected_phase);
+        assert!((moment.rd() - t.to_moment().rd()).abs() < 1e-8);
+    }
+
+    let start = FixedDate::from_gregorian(2020, 11, 1).unwrap();
+    let end = FixedDate::from_gregorian(2020, 10, 1).unwrap();
+    let days = daily_lunar_phase_iter(start..end).map(|(_, date)| date).collect::<Vec<_>>();
+    let start = NaiveDate::from_ymd_opt(2020, 11, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2020, 10, 1).unwrap();
+    let expected =
+        daily_lunar_phase_iter(start..end).map(|(_, date)| date.fixed_date()).collect::<Vec<_>>();
+    assert_eq!(days, expected);
+}
 
 #[cfg(test)]
 #[test]
 fn test_daily_iter_rev() {
-    use chrono::TimeZone;
-
-    let start = Utc.ymd(2020, 11, 1);
-    let end = Utc.ymd(2020, 10, 1);
-    let mut iter = daily_lunar_phase_iter(start..end);
+    let date = |m, d| FixedDate::from_gregorian(2020, m, d).unwrap();
+    let mut iter = daily_lunar_phase_iter(date(11, 1)..date(10, 1));
 
-    assert_eq!(iter.next().unwrap(), (PrincipalPhase::FullMoon, Utc.ymd(2020, 10, 31)));
-    assert_eq!(iter.next().unwrap(), (PrincipalPhase::FirstQuarter, Utc.ymd(2020, 10, 23)));
-    assert_eq!(iter.next().unwrap(), (PrincipalPhase::NewMoon, Utc.ymd(2020, 10, 16)));
-    assert_eq!(iter.next().unwrap(), (PrincipalPhase::LastQuarter, Utc.ymd(2020, 10, 10)));
-    assert_eq!(iter.next().unwrap(), (PrincipalPhase::FullMoon, Utc.ymd(2020, 10, 1)));
+    assert_eq!(iter.next().unwrap(), (PrincipalPhase::FullMoon, date(10, 31)));
+    assert_eq!(iter.next().unwrap(), (PrincipalPhase::FirstQuarter, date(10, 23)));
+    assert_eq!(iter.next().unwrap(), (PrincipalPhase::NewMoon, date(10, 16)));
+    assert_eq!(iter.next().unwrap(), (PrincipalPhase::LastQuarter, date(10, 10)));
+    assert_eq!(iter.next().unwrap(), (PrincipalPhase::FullMoon, date(10, 1)));
     assert!(iter.next().is_none());
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_daily_iter_in() {
+    use crate::conv::TestZone;
+    use crate::phase::Phase;
+    use chrono::{DateTime, NaiveDate};
+
+    let date = |m, d| NaiveDate::from_ymd_opt(2020, m, d).unwrap();
+    let phases = daily_lunar_phase_iter_in(date(10, 1)..date(12, 1), TestZone).collect::<Vec<_>>();
+    // The full moon at 14:49 UTC on October 31 is at 01:49 on November 1, the day the clocks go
+    // forward at midnight
+    assert_eq!(phases[4].0, PrincipalPhase::FullMoon);
+    assert_eq!(phases[4].1.date_naive(), date(11, 1));
+    for (phase, t) in &phases {
+        assert_eq!(crate::daily_lunar_phase(*t), Phase::from(*phase));
+    }
+
+    let days = |iter: DailyIter<DateTime<TestZone>>| {
+        iter.map(|(phase, t)| (phase, t.date_naive())).collect::<Vec<_>>()
+    };
+    let mut rev = days(daily_lunar_phase_iter_in(date(12, 1)..date(10, 1), TestZone));
+    rev.reverse();
+    assert_eq!(rev, days(daily_lunar_phase_iter_in(date(10, 1)..date(12, 1), TestZone)));
+}
+
+#[cfg(all(test, feature = "chrono"))]
 #[test]
 fn test_ranges() {
-    use chrono::TimeZone;
+    use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 
-    let start = Utc.ymd(2020, 10, 1).and_hms(0, 0, 0);
-    let end = Utc.ymd(2020, 11, 1).and_hms(0, 0, 0);
-    lunar_phase_iter(..);
+    let start = Utc.with_ymd_and_hms(2020, 10, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2020, 11, 1, 0, 0, 0).unwrap();
+    lunar_phase_iter::<DateTime<Utc>, _>(..);
     lunar_phase_iter(start..);
     lunar_phase_iter(..end);
     lunar_phase_iter(..=end);
     lunar_phase_iter(start..end);
     lunar_phase_iter(start..=end);
 
-    let start = Utc.ymd(2020, 10, 1);
-    let end = Utc.ymd(2020, 11, 1);
-    daily_lunar_phase_iter(..);
+    let start = NaiveDate::from_ymd_opt(2020, 10, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2020, 11, 1).unwrap();
+    daily_lunar_phase_iter::<NaiveDate, _>(..);
     daily_lunar_phase_iter(start..);
     daily_lunar_phase_iter(..end);
     daily_lunar_phase_iter(..=end);
     daily_lunar_phase_iter(start..end);
     daily_lunar_phase_iter(start..=end);
+    daily_lunar_phase_iter_in::<NaiveDate, _, _>(.., Utc);
+    daily_lunar_phase_iter_in(start.., Utc);
+    daily_lunar_phase_iter_in(..end, Utc);
+    daily_lunar_phase_iter_in(..=end, Utc);
+    daily_lunar_phase_iter_in(start..end, Utc);
+    daily_lunar_phase_iter_in(start..=end, Utc);
 }
@@ -21,10 +21,55 @@
 //! Calculations</i>. Please contact the author of this crate at
 //! [iliana@buttslol.net][mail] for any licensing questions.
 //!
+//! # Date-time libraries
+//!
+//! Every function works with the crate's own [`Moment`] and [`FixedDate`] types. Support for the
+//! types of other date-time libraries is enabled with cargo features:
+//!
+//! - `chrono` (enabled by default, and requires `alloc`): Chrono's `DateTime`, `NaiveDate` and
+//!   time zones
+//! - `time`: the `time` crate's `OffsetDateTime`, `Date` and `UtcOffset`
+//! - `jiff`: Jiff's `Timestamp`, `Zoned`, civil `Date` and `TimeZone`
+//!
+//! Types that aren't otherwise specified default to [`Moment`] and [`FixedDate`].
+//!
+//! # `no_std`
+//!
+//! The `std` feature is enabled by default. Without it, this crate is `#![no_std]` and requires the
+//! `libm` feature for its floating-point math. The `chrono` feature enables the `alloc` feature, so
+//! it needs an allocator; the `time` and `jiff` integrations need neither the standard library nor
+//! an allocator. The `std` and `alloc` features enable the corresponding features of whichever
+//! date-time libraries are in use.
+//!
+//! ```toml
+//! [dependencies]
+//! esbat = { version = "0.1", default-features = false, features = ["libm"] }
+//! ```
+//!
+//! # Reproducibility
+//!
+//! By default, trigonometric functions come from the platform's math library, so results can
+//! differ in their last bits between targets. The `deterministic` feature uses libm, a pure-Rust
+//! implementation, for all floating-point math, so that every function returns identical results
+//! on every target with IEEE 754 double-precision arithmetic.
+//!
+//! # ΔT
+//!
+//! Positions of the Sun and Moon are calculated in Terrestrial Time and converted to Universal
+//! Time with an estimate of ΔT, the difference between the two. Every function uses the
+//! polynomials of Espenak and Meeus ([`EspenakMeeus`]), which are fitted to the years -500 to 2150.
+//! For other periods, or to use published values, an [`Ephemeris`] performs every calculation that
+//! depends on ΔT with any [`DeltaT`] model: [`MorrisonStephenson2004`], a [`DeltaTTable`] of values
+//! read from a file (with the `alloc` feature), a [`FixedDeltaT`], or your own. The Hebrew and
+//! Tibetan calendars are arithmetic and do not depend on ΔT.
+//!
 //! [book]: https://doi.org/10.1017/9781107415058
 //! [license]: https://creativecommons.org/licenses/by-nc/4.0/
 //! [mail]: mailto:iliana@buttslol.net
 
+// Without either `std` or `libm`, the standard library is still linked so that the only error is
+// the `compile_error!` below
+#![cfg_attr(all(feature = "libm", not(any(feature = "std", test))), no_std)]
 #![warn(
     absolute_paths_not_starting_with_crate,
     trivial_casts,
@@ -38,18 +83,81 @@
     clippy::needless_pass_by_value
 )]
 
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the `std` or `libm` feature must be enabled");
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod apsis;
+mod babylonian;
+mod blue;
 mod calendar;
+mod chinese;
+#[cfg(feature = "chrono")]
 mod conv;
+#[cfg(feature = "jiff")]
+mod conv_jiff;
+#[cfg(feature = "time")]
+mod conv_time;
 mod data;
+mod delta_t;
+mod easter;
+mod eclipse;
+mod ephemeris;
+mod hebrew;
+mod islamic;
 mod iter;
+mod location;
+mod lunation;
+mod moment;
+mod name;
+mod panchanga;
 mod phase;
+mod rise;
+mod sabbat;
+mod season;
+mod state;
+mod tibetan;
 mod util;
 
-pub use crate::iter::{daily_lunar_phase_iter, lunar_phase_iter, DailyIter, Iter};
+pub use crate::apsis::{apsis_iter, moon_size, Apsis, ApsisIter, MoonSize, SupermoonRule};
+pub use crate::babylonian::BabylonianDate;
+pub use crate::blue::{is_black_moon, is_blue_moon, is_seasonal_black_moon, is_seasonal_blue_moon};
+pub use crate::chinese::{
+    chinese_new_year, mid_autumn_festival, solar_term_iter, CelestialStem, ChineseDate,
+    EarthlyBranch, SolarTerm, SolarTermIter,
+};
+#[cfg(feature = "alloc")]
+pub use crate::delta_t::DeltaTTable;
+pub use crate::delta_t::{DeltaT, EspenakMeeus, FixedDeltaT, MorrisonStephenson2004};
+pub use crate::easter::{easter, Computus};
+pub use crate::eclipse::{
+    eclipse_iter, Eclipse, EclipseIter, EclipseKind, LunarEclipseKind, SolarEclipseKind,
+};
+pub use crate::ephemeris::Ephemeris;
+pub use crate::hebrew::{molad, HebrewDate, HebrewHoliday, Molad};
+pub use crate::islamic::{
+    is_crescent_visible, ramadan, CrescentCriterion, IslamicCalendar, IslamicDate,
+};
+pub use crate::iter::{
+    daily_lunar_phase_iter, daily_lunar_phase_iter_in, lunar_phase_iter, DailyIter, Iter,
+};
+pub use crate::location::{Hemisphere, Location};
+pub use crate::lunation::{lunation_iter, Lunation, LunationIter, LunationNumbering};
+pub use crate::moment::{CivilDate, Day, FixedDate, LocalDate, Moment, Time, Timestamp, Weekday};
+pub use crate::name::{full_moon_name, FullMoonName, FullMoonTradition};
+pub use crate::panchanga::{
+    panchanga, tithi_iter, Ayanamsha, Karana, Nakshatra, Paksha, Panchanga, Tithi, TithiIter, Yoga,
+};
 pub use crate::phase::{Phase, PrincipalPhase};
-
-use crate::conv::fixed_from_chrono;
-use chrono::{Date, DateTime, Duration, TimeZone, Utc};
+pub use crate::rise::{dawn, dusk, moonrise, moonset, sunrise, sunset, Twilight};
+pub use crate::sabbat::{
+    sabbat_iter, wheel_iter, Sabbat, SabbatIter, SabbatMode, WheelEvent, WheelIter,
+};
+pub use crate::season::{season_iter, solar_longitude_after, Season, SeasonIter};
+pub use crate::state::{lunar_state, LunarState};
+pub use crate::tibetan::{losar, TibetanDate};
 
 /// Calculates the lunar phase for a given moment.
 ///
@@ -59,15 +167,100 @@ use chrono::{Date, DateTime, Duration, TimeZone, Utc};
 /// The new moon is 0&deg;, the first-quarter moon is 90&deg;, the full moon is 180&deg;, and the
 /// last-quarter moon is 270&deg;.
 ///
+/// Like every function in this crate that takes a moment, `t` can be a Chrono `DateTime` in any
+/// time zone, a `time` crate `OffsetDateTime`, a Jiff `Timestamp` or `Zoned`, or a [`Moment`].
+///
 /// ```
-/// use chrono::{DateTime, TimeZone, Utc};
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{Duration, TimeZone, Utc};
 /// use esbat::lunar_phase;
 ///
-/// let t = Utc.ymd(2020, 10, 31).and_hms_milli(14, 48, 59, 300);
+/// let t = Utc.with_ymd_and_hms(2020, 10, 31, 14, 48, 59).unwrap() + Duration::milliseconds(300);
 /// assert!((lunar_phase(t) - 180.0).abs() < 0.00001);
+/// # }
+/// ```
+pub fn lunar_phase<T: Time>(t: T) -> f64 {
+    calendar::lunar_phase(t.to_moment().rd(), &EspenakMeeus)
+}
+
+/// Calculates the solar longitude for a given moment.
+///
+/// This is the Sun's apparent position along the ecliptic, in degrees, measured from the March
+/// equinox. The result is clamped to 0&deg;&nbsp;&le;&nbsp;<i>x</i>&nbsp;&lt;&nbsp;360&deg;.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{TimeZone, Utc};
+/// use esbat::solar_longitude;
+///
+/// let t = Utc.with_ymd_and_hms(2020, 6, 20, 21, 44, 0).unwrap();
+/// assert!((solar_longitude(t) - 90.0).abs() < 0.001);
+/// # }
+/// ```
+pub fn solar_longitude<T: Time>(t: T) -> f64 {
+    calendar::solar_longitude(t.to_moment().rd(), &EspenakMeeus)
+}
+
+/// Calculates the illuminated fraction of the Moon's disc for a given moment.
+///
+/// The result ranges from 0.0 (nothing is lit, as at a new moon) to 1.0 (the entire disc is lit,
+/// as at a full moon).
+///
 /// ```
-pub fn lunar_phase<Tz: TimeZone>(t: DateTime<Tz>) -> f64 {
-    calendar::lunar_phase(fixed_from_chrono(t.with_timezone(&Utc)))
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{Duration, TimeZone, Utc};
+/// use esbat::lunar_illumination;
+///
+/// // Meeus, Astronomical Algorithms, 2nd ed., example 48.a
+/// let t = Utc.with_ymd_and_hms(1992, 4, 11, 23, 59, 1).unwrap();
+/// assert!((lunar_illumination(t) - 0.6786).abs() < 0.0001);
+///
+/// let t = Utc.with_ymd_and_hms(2020, 10, 31, 14, 48, 59).unwrap() + Duration::milliseconds(300);
+/// assert!(lunar_illumination(t) > 0.999);
+/// # }
+/// ```
+pub fn lunar_illumination<T: Time>(t: T) -> f64 {
+    calendar::lunar_illumination(t.to_moment().rd(), &EspenakMeeus)
+}
+
+/// Calculates the distance between the centers of the Earth and the Moon for a given moment, in
+/// kilometers.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{TimeZone, Utc};
+/// use esbat::lunar_distance;
+///
+/// // Meeus, Astronomical Algorithms, 2nd ed., example 47.a
+/// let t = Utc.with_ymd_and_hms(1992, 4, 11, 23, 59, 1).unwrap();
+/// assert!((lunar_distance(t) - 368_409.7).abs() < 0.1);
+/// # }
+/// ```
+pub fn lunar_distance<T: Time>(t: T) -> f64 {
+    calendar::lunar_distance(t.to_moment().rd(), &EspenakMeeus) / 1000.0
+}
+
+/// Calculates the position angle of the Moon's bright limb for a given moment.
+///
+/// This is the angle, in degrees, of the midpoint of the illuminated limb of the Moon, measured
+/// eastward from the north point of the disc. The terminator is perpendicular to this angle. The
+/// result is clamped to 0&deg;&nbsp;&le;&nbsp;<i>x</i>&nbsp;&lt;&nbsp;360&deg;.
+///
+/// A waxing Moon's bright limb faces west toward the setting Sun, near 270&deg;, and a waning
+/// Moon's bright limb faces east, near 90&deg;.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{TimeZone, Utc};
+/// use esbat::bright_limb_angle;
+///
+/// // Meeus, Astronomical Algorithms, 2nd ed., example 48.a
+/// let t = Utc.with_ymd_and_hms(1992, 4, 11, 23, 59, 1).unwrap();
+/// assert!((bright_limb_angle(t) - 285.0).abs() < 0.1);
+/// # }
+/// ```
+pub fn bright_limb_angle<T: Time>(t: T) -> f64 {
+    calendar::bright_limb_angle(t.to_moment().rd(), &EspenakMeeus)
 }
 
 /// Calculates the lunar phase for a given date.
@@ -76,14 +269,32 @@ pub fn lunar_phase<Tz: TimeZone>(t: DateTime<Tz>) -> f64 {
 /// occurs on the date `t` or the intermediate phase between the previous and next principal
 /// phases.
 ///
+/// `t` can be a civil date such as Chrono's `NaiveDate` (a day in UTC), a [`FixedDate`] (a day in
+/// Universal Time), or a moment in a time zone such as Chrono's `DateTime` or Jiff's `Zoned`, which
+/// stands for the whole day it falls on in its own time zone: from local midnight to the next local
+/// midnight, which may be 23 or 25 hours later.
+///
 /// ```
-/// use chrono::{Date, TimeZone, Utc};
-/// use esbat::{Phase, daily_lunar_phase};
+/// use esbat::{FixedDate, Phase, daily_lunar_phase};
+///
+/// let t = FixedDate::from_gregorian(2020, 10, 31).unwrap();
+/// assert_eq!(daily_lunar_phase(t), Phase::FullMoon);
+/// # #[cfg(feature = "chrono")] {
+///
+/// use chrono::{FixedOffset, NaiveDate, TimeZone};
+///
+/// let t = NaiveDate::from_ymd_opt(2020, 10, 31).unwrap();
+/// assert_eq!(daily_lunar_phase(t), Phase::FullMoon);
 ///
-/// let t = Utc.ymd(2020, 10, 31);
+/// // The same full moon falls on November 1 at UTC+10
+/// let tz = FixedOffset::east_opt(10 * 3600).unwrap();
+/// let t = tz.with_ymd_and_hms(2020, 10, 31, 12, 0, 0).unwrap();
+/// assert_eq!(daily_lunar_phase(t), Phase::WaxingGibbous);
+/// let t = tz.with_ymd_and_hms(2020, 11, 1, 12, 0, 0).unwrap();
 /// assert_eq!(daily_lunar_phase(t), Phase::FullMoon);
+/// # }
 /// ```
-pub fn daily_lunar_phase<Tz: TimeZone>(t: Date<Tz>) -> Phase {
-    let t = t.and_hms(0, 0, 0).with_timezone(&Utc);
-    Phase::from_phase_range(lunar_phase(t), lunar_phase(t + Duration::days(1)))
+pub fn daily_lunar_phase<D: Day>(t: D) -> Phase {
+    let (start, end) = t.bounds();
+    Phase::from_phase_range(lunar_phase(start), lunar_phase(end))
 }
@@ -0,0 +1,76 @@
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+
+/// An observer's location on the surface of the Earth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Location {
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+}
+
+impl Location {
+    /// Creates a location from its latitude and longitude, in degrees, and its elevation above
+    /// sea level, in meters.
+    ///
+    /// Latitudes north of the equator and longitudes east of the prime meridian are positive.
+    ///
+    /// ```
+    /// # use esbat::Location;
+    /// let greenwich = Location::new(51.4778, -0.0014, 46.0);
+    /// assert_eq!(greenwich.latitude(), 51.4778);
+    /// ```
+    pub fn new(latitude: f64, longitude: f64, elevation: f64) -> Location {
+        debug_assert!((-90.0..=90.0).contains(&latitude));
+        debug_assert!((-180.0..=180.0).contains(&longitude));
+        Location { latitude, longitude, elevation }
+    }
+
+    /// Returns the latitude of the location, in degrees.
+    pub fn latitude(self) -> f64 {
+        self.latitude
+    }
+
+    /// Returns the longitude of the location, in degrees.
+    pub fn longitude(self) -> f64 {
+        self.longitude
+    }
+
+    /// Returns the elevation of the location above sea level, in meters.
+    pub fn elevation(self) -> f64 {
+        self.elevation
+    }
+
+    /// Returns the hemisphere the location is in.
+    ///
+    /// Locations on the equator are considered to be in the northern hemisphere.
+    ///
+    /// ```
+    /// # use esbat::{Hemisphere, Location};
+    /// let wellington = Location::new(-41.2865, 174.7762, 0.0);
+    /// assert_eq!(wellington.hemisphere(), Hemisphere::Southern);
+    /// ```
+    pub fn hemisphere(self) -> Hemisphere {
+        if self.latitude < 0.0 {
+            Hemisphere::Southern
+        } else {
+            Hemisphere::Northern
+        }
+    }
+}
+
+/// A hemisphere of the Earth, north or south of the equator.
+///
+/// Seasonal observances are reversed between the hemispheres; the December solstice is midwinter in
+/// the northern hemisphere and midsummer in the southern hemisphere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Hemisphere {
+    /// The hemisphere north of the equator.
+    Northern,
+    /// The hemisphere south of the equator.
+    Southern,
+}
@@ -0,0 +1,298 @@
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+
+use crate::calendar::{new_moon_index_at_or_before, nth_new_moon};
+use crate::delta_t::{DeltaT, EspenakMeeus};
+use crate::iter::moment_bounds;
+use crate::moment::{Moment, Time, Timestamp};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::RangeBounds;
+
+// The index passed to `nth_new_moon` for Meeus's lunation 0, the new moon of January 6, 2000
+const MEEUS_EPOCH: i32 = 24724;
+
+/// A convention for numbering lunations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LunationNumbering {
+    /// Lunation 0 begins with the new moon of January 6, 2000, as used by Jean Meeus in
+    /// <i>Astronomical Algorithms</i>.
+    Meeus,
+    /// Lunation 1 begins with the new moon of January 17, 1923, as defined by Ernest William
+    /// Brown.
+    Brown,
+    /// Lunation 0 begins with the new moon of January 11, 1001 BCE, as used by Herman Goldstine in
+    /// <i>New and Full Moons 1001 B.C. to A.D. 1651</i>.
+    Goldstine,
+    /// Lunation 1 begins with the molad of Tishri in year 1 of the Hebrew calendar.
+    Hebrew,
+    /// Lunation 1 begins with the month of Muharram in year 1 of the Islamic calendar (July 622).
+    Islamic,
+    /// Lunation 0 begins with the epoch of the Thai lunar calendar (March 638).
+    Thai,
+}
+
+impl LunationNumbering {
+    fn offset(self) -> i32 {
+        match self {
+            LunationNumbering::Meeus => 0,
+            LunationNumbering::Brown => 953,
+            LunationNumbering::Goldstine => 37105,
+            LunationNumbering::Hebrew => 71234,
+            LunationNumbering::Islamic => 17038,
+            LunationNumbering::Thai => 16843,
+        }
+    }
+}
+
+/// A lunation (synodic month): the period from one new moon to the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lunation<T = Moment> {
+    n: i32,
+    start: T,
+    end: T,
+}
+
+impl<T: Time> Lunation<T> {
+    // Returns lunation `n`, with its moments in the time zone of `t`
+    fn from_index_in(n: i32, t: &T, dt: &dyn DeltaT) -> Option<Lunation<T>> {
+        Some(Lunation {
+            n,
+            start: t.with_moment(Moment::from_rd(nth_new_moon(n, dt)))?,
+            end: t.with_moment(Moment::from_rd(nth_new_moon(n.checked_add(1)?, dt)))?,
+        })
+    }
+
+    /// Returns the lunation containing moment `t`, with its moments in the time zone of `t`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lunation begins or ends outside of the range of times the type of `t` can
+    /// represent.
+    ///
+    /// ```
+    /// use esbat::{FixedDate, Lunation, LunationNumbering};
+    ///
+    /// let date = |m, d| FixedDate::from_gregorian(2020, m, d).unwrap();
+    /// let lunation = Lunation::containing(date(10, 31).moment());
+    /// assert_eq!(lunation.number(LunationNumbering::Brown), 1210);
+    /// assert_eq!(lunation.start().fixed_date(), date(10, 16));
+    /// assert_eq!(lunation.end().fixed_date(), date(11, 15));
+    /// ```
+    pub fn containing(t: T) -> Lunation<T> {
+        Lunation::containing_with(t, &EspenakMeeus)
+    }
+
+    pub(crate) fn containing_with(t: T, dt: &dyn DeltaT) -> Lunation<T> {
+        let (n, start, end, _) = new_moon_index_at_or_before(t.to_moment().rd(), dt);
+        Lunation {
+            n,
+            start: t.with_moment(Moment::from_rd(start)).unwrap(),
+            end: t.with_moment(Moment::from_rd(end)).unwrap(),
+        }
+    }
+
+    /// Returns the number of the lunation in a numbering convention.
+    pub fn number(&self, numbering: LunationNumbering) -> i32 {
+        self.n - MEEUS_EPOCH + numbering.offset()
+    }
+
+    /// Returns the moment of the new moon that begins the lunation.
+    pub fn start(&self) -> T {
+        self.start.clone()
+    }
+
+    /// Returns the moment of the new moon that ends the lunation (and begins the next).
+    pub fn end(&self) -> T {
+        self.end.clone()
+    }
+
+    /// Returns the next lunation, calculated with [`EspenakMeeus`]. To use another ΔT model, see
+    /// [`Ephemeris::lunation_succ`](crate::Ephemeris::lunation_succ).
+    ///
+    /// Returns `None` if the lunation cannot be represented by `T`.
+    pub fn succ(&self) -> Option<Lunation<T>> {
+        self.succ_with(&EspenakMeeus)
+    }
+
+    pub(crate) fn succ_with(&self, dt: &dyn DeltaT) -> Option<Lunation<T>> {
+        Lunation::from_index_in(self.n.checked_add(1)?, &self.start, dt)
+    }
+
+    /// Returns the previous lunation, calculated with [`EspenakMeeus`]. To use another ΔT model,
+    /// see [`Ephemeris::lunation_pred`](crate::Ephemeris::lunation_pred).
+    ///
+    /// Returns `None` if the lunation cannot be represented by `T`.
+    pub fn pred(&self) -> Option<Lunation<T>> {
+        self.pred_with(&EspenakMeeus)
+    }
+
+    pub(crate) fn pred_with(&self, dt: &dyn DeltaT) -> Option<Lunation<T>> {
+        Lunation::from_index_in(self.n.checked_sub(1)?, &self.start, dt)
+    }
+}
+
+impl<T: Timestamp> Lunation<T> {
+    fn from_index(n: i32, dt: &dyn DeltaT) -> Option<Lunation<T>> {
+        Some(Lunation {
+            n,
+            start: T::from_moment(Moment::from_rd(nth_new_moon(n, dt)))?,
+            end: T::from_moment(Moment::from_rd(nth_new_moon(n.checked_add(1)?, dt)))?,
+        })
+    }
+
+    /// Returns the lunation with the given number in a numbering convention.
+    ///
+    /// Returns `None` if the lunation cannot be represented by `T`.
+    ///
+    /// ```
+    /// use esbat::{FixedDate, Lunation, LunationNumbering};
+    ///
+    /// let lunation: Lunation = Lunation::from_number(0, LunationNumbering::Meeus).unwrap();
+    /// assert_eq!(lunation.start().fixed_date(), FixedDate::from_gregorian(2000, 1, 6).unwrap());
+    /// assert_eq!(lunation.number(LunationNumbering::Islamic), 17038);
+    /// ```
+    pub fn from_number(number: i32, numbering: LunationNumbering) -> Option<Lunation<T>> {
+        Lunation::from_number_with(number, numbering, &EspenakMeeus)
+    }
+
+    pub(crate) fn from_number_with(
+        number: i32,
+        numbering: LunationNumbering,
+        dt: &dyn DeltaT,
+    ) -> Option<Lunation<T>> {
+        let n = number.checked_sub(numbering.offset())?.checked_add(MEEUS_EPOCH)?;
+        Lunation::from_index(n, dt)
+    }
+}
+
+/// Returns an iterator of the lunations that begin within a range.
+///
+/// ```
+/// use esbat::{FixedDate, LunationNumbering};
+///
+/// let start = FixedDate::from_gregorian(2020, 1, 1).unwrap().moment();
+/// let end = FixedDate::from_gregorian(2021, 1, 1).unwrap().moment();
+/// let mut iter = esbat::lunation_iter(start..end);
+///
+/// assert_eq!(iter.next().unwrap().number(LunationNumbering::Meeus), 248);
+/// assert_eq!(iter.count(), 11);
+///
+/// let mut iter = esbat::lunation_iter(end..start);
+/// assert_eq!(iter.next().unwrap().number(LunationNumbering::Meeus), 259);
+/// ```
+pub fn lunation_iter<T, B>(range: B) -> LunationIter<T>
+where
+    T: Timestamp,
+    B: RangeBounds<T>,
+{
+    let (start, end) = moment_bounds(&range);
+    LunationIter::new(start, end, EspenakMeeus)
+}
+
+/// Lunation iterator.
+///
+/// This struct is created by [`lunation_iter`] or
+/// [`Ephemeris::lunation_iter`](crate::Ephemeris::lunation_iter), which determines the ΔT model
+/// `M`.
+#[derive(Debug, Clone)]
+pub struct LunationIter<T = Moment, M = EspenakMeeus> {
+    bound: Option<(i32, i32)>,
+    positive: bool,
+    model: M,
+    time: PhantomData<T>,
+}
+
+impl<T: Timestamp, M: DeltaT> LunationIter<T, M> {
+    pub(crate) fn new(
+        (start, start_excl): (f64, bool),
+        (end, end_excl): (f64, bool),
+        model: M,
+    ) -> LunationIter<T, M> {
+        let positive = start <= end;
+
+        let in_range = |t: f64, bound: f64, excl: bool, after: bool| {
+            if after {
+                t > bound || (t >= bound && !excl)
+            } else {
+                t < bound || (t <= bound && !excl)
+            }
+        };
+
+        let (mut n, new_moon, _, _) = new_moon_index_at_or_before(start, &model);
+        if positive && !in_range(new_moon, start, start_excl, true) {
+            n += 1;
+        } else if !positive && !in_range(new_moon, start, start_excl, false) {
+            n -= 1;
+        }
+        let (mut end_n, end_new_moon, _, _) = new_moon_index_at_or_before(end, &model);
+        if !in_range(end_new_moon, end, end_excl, !positive) {
+            end_n += if positive { -1 } else { 1 };
+        }
+
+        LunationIter { bound: Some((n, end_n)), positive, model, time: PhantomData }
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> Iterator for LunationIter<T, M> {
+    type Item = Lunation<T>;
+
+    fn next(&mut self) -> Option<Lunation<T>> {
+        let (n, end) = self.bound?;
+        if (self.positive && n <= end) || (!self.positive && n >= end) {
+            if let Some(lunation) = Lunation::from_index(n, &self.model) {
+                self.bound = Some((if self.positive { n + 1 } else { n - 1 }, end));
+                return Some(lunation);
+            }
+        }
+        self.bound = None;
+        None
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> FusedIterator for LunationIter<T, M> {}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_lunation() {
+    use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+    use core::ops::Bound::{Excluded, Included};
+
+    let t = Utc.with_ymd_and_hms(2000, 1, 6, 18, 14, 0).unwrap();
+    let lunation = Lunation::containing(t);
+    assert_eq!(lunation.number(LunationNumbering::Meeus), 0);
+    assert_eq!(lunation.number(LunationNumbering::Brown), 953);
+    assert!((lunation.start() - t).num_minutes().abs() <= 2);
+    assert_eq!(Lunation::containing(lunation.end()).pred(), Some(lunation));
+    assert_eq!(Lunation::containing(lunation.start()), lunation);
+    assert_eq!(Lunation::containing(lunation.end()), lunation.succ().unwrap());
+
+    let brown_one: Lunation<DateTime<Utc>> =
+        Lunation::from_number(1, LunationNumbering::Brown).unwrap();
+    assert_eq!(brown_one.start().date_naive(), NaiveDate::from_ymd_opt(1923, 1, 17).unwrap());
+
+    for numbering in &[
+        LunationNumbering::Meeus,
+        LunationNumbering::Brown,
+        LunationNumbering::Goldstine,
+        LunationNumbering::Hebrew,
+        LunationNumbering::Islamic,
+        LunationNumbering::Thai,
+    ] {
+        let n = lunation.number(*numbering);
+        assert_eq!(Lunation::from_number(n, *numbering), Some(lunation));
+    }
+
+    let start = lunation.start();
+    let end = lunation.succ().unwrap().succ().unwrap().start();
+    assert_eq!(lunation_iter(start..end).count(), 2);
+    assert_eq!(lunation_iter(start..=end).count(), 3);
+    assert_eq!(lunation_iter((Excluded(start), Included(end))).count(), 2);
+    assert_eq!(lunation_iter(end..start).count(), 2);
+    assert_eq!(lunation_iter(end..=start).count(), 3);
+    assert_eq!(lunation_iter(end..=start).last(), Some(lunation));
+}
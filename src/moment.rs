@@ -0,0 +1,412 @@
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+
+use crate::calendar::{fixed_from_gregorian, gregorian_from_fixed};
+use crate::util::{floor, i32, nonneg, rem_euclid, u32};
+
+// The R.D. moments of the other epochs
+const JD_EPOCH: f64 = -1_721_424.5;
+const MJD_EPOCH: f64 = 678_576.0;
+const UNIX_EPOCH: f64 = 719_163.0;
+const J2000: f64 = 730_120.5;
+
+// The range of moments accepted by the time-generic functions, about 274,000 years either side of
+// R.D. 0
+const MIN_MOMENT: f64 = -100_000_000.0;
+const MAX_MOMENT: f64 = 100_000_000.0;
+
+// The range of years accepted by `FixedDate::from_gregorian`, whose dates have an R.D. that fits in
+// an `i32`
+pub(crate) const MIN_YEAR: i32 = -5_879_000;
+pub(crate) const MAX_YEAR: i32 = 5_879_000;
+
+/// A moment in time, in Universal Time.
+///
+/// A moment is a count of days and fractions of days since the beginning of the Rata Die (R.D.)
+/// epoch: 1.0 R.D. is midnight at the start of Monday, January 1, 1 in the proleptic Gregorian
+/// calendar. Moments can be used with every function in this crate in place of a date-time type.
+///
+/// ```
+/// use esbat::Moment;
+///
+/// let t = Moment::from_unix_timestamp(1_604_155_739.3);
+/// assert!((t.jd() - 2_459_154.117_353).abs() < 0.000001);
+/// assert!((esbat::lunar_phase(t) - 180.0).abs() < 0.00001);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Moment(f64);
+
+impl Moment {
+    /// Creates a moment from a count of days since the R.D. epoch.
+    pub fn from_rd(rd: f64) -> Moment {
+        Moment(rd)
+    }
+
+    /// Returns the count of days since the R.D. epoch.
+    pub fn rd(self) -> f64 {
+        self.0
+    }
+
+    /// Creates a moment from a Julian Day: the count of days since noon on January 1, 4713 BCE in
+    /// the proleptic Julian calendar.
+    ///
+    /// ```
+    /// use esbat::Moment;
+    ///
+    /// assert_eq!(Moment::from_jd(2_451_545.0), Moment::from_j2000(0.0));
+    /// ```
+    pub fn from_jd(jd: f64) -> Moment {
+        Moment(jd + JD_EPOCH)
+    }
+
+    /// Returns the Julian Day of the moment.
+    pub fn jd(self) -> f64 {
+        self.0 - JD_EPOCH
+    }
+
+    /// Creates a moment from a Modified Julian Day: the count of days since midnight on November
+    /// 17, 1858.
+    ///
+    /// ```
+    /// use esbat::{FixedDate, Moment};
+    ///
+    /// let t = Moment::from_mjd(59_153.0);
+    /// assert_eq!(t.fixed_date().to_gregorian(), (2020, 10, 31));
+    /// ```
+    pub fn from_mjd(mjd: f64) -> Moment {
+        Moment(mjd + MJD_EPOCH)
+    }
+
+    /// Returns the Modified Julian Day of the moment.
+    pub fn mjd(self) -> f64 {
+        self.0 - MJD_EPOCH
+    }
+
+    /// Creates a moment from a Unix timestamp: the count of seconds since midnight on January 1,
+    /// 1970, not counting leap seconds.
+    pub fn from_unix_timestamp(seconds: f64) -> Moment {
+        Moment(seconds / 86400.0 + UNIX_EPOCH)
+    }
+
+    /// Returns the Unix timestamp of the moment, in seconds.
+    pub fn unix_timestamp(self) -> f64 {
+        (self.0 - UNIX_EPOCH) * 86400.0
+    }
+
+    /// Creates a moment from a count of days since the J2000 epoch, noon on January 1, 2000.
+    ///
+    /// The epoch is taken in Universal Time, like all moments in this crate, rather than in
+    /// Terrestrial Time as in astronomical tables.
+    pub fn from_j2000(days: f64) -> Moment {
+        Moment(days + J2000)
+    }
+
+    /// Returns the count of days since the J2000 epoch.
+    pub fn j2000(self) -> f64 {
+        self.0 - J2000
+    }
+
+    /// Returns the date the moment falls on.
+    pub fn fixed_date(self) -> FixedDate {
+        FixedDate(i32(floor(self.0)))
+    }
+}
+
+/// A date, as a count of days since the Rata Die (R.D.) epoch.
+///
+/// 1 R.D. is Monday, January 1, 1 in the proleptic Gregorian calendar. Fixed dates can be used with
+/// every function in this crate in place of a date type, and are the basis of all of its calendar
+/// conversions.
+///
+/// ```
+/// use esbat::{FixedDate, HebrewDate};
+///
+/// let date = FixedDate::from_gregorian(2020, 9, 19).unwrap();
+/// assert_eq!(date.rd(), 737_687);
+/// assert_eq!(HebrewDate::from_gregorian(date), HebrewDate { year: 5781, month: 7, day: 1 });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedDate(i32);
+
+impl FixedDate {
+    /// Creates a date from a count of days since the R.D. epoch.
+    pub fn from_rd(rd: i32) -> FixedDate {
+        FixedDate(rd)
+    }
+
+    /// Returns the count of days since the R.D. epoch.
+    pub fn rd(self) -> i32 {
+        self.0
+    }
+
+    /// Creates a date from a year, month and day in the proleptic Gregorian calendar.
+    ///
+    /// Returns `None` if the month or day is out of range, or if the year is more than 5,879,000
+    /// years from 1 CE.
+    pub fn from_gregorian(year: i32, month: u32, day: u32) -> Option<FixedDate> {
+        if !(MIN_YEAR..=MAX_YEAR).contains(&year)
+            || !(1..=12).contains(&month)
+            || !(1..=31).contains(&day)
+        {
+            return None;
+        }
+        let date = FixedDate(fixed_from_gregorian(year, month, day));
+        if date.to_gregorian() == (year, month, day) {
+            Some(date)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the year, month and day of the date in the proleptic Gregorian calendar.
+    pub fn to_gregorian(self) -> (i32, u32, u32) {
+        gregorian_from_fixed(f64::from(self.0))
+    }
+
+    /// Returns the moment of midnight at the start of the date.
+    pub fn moment(self) -> Moment {
+        Moment(f64::from(self.0))
+    }
+
+    /// Returns the day of the week.
+    ///
+    /// ```
+    /// use esbat::{FixedDate, Weekday};
+    ///
+    /// assert_eq!(FixedDate::from_gregorian(2020, 10, 31).unwrap().weekday(), Weekday::Saturday);
+    /// ```
+    pub fn weekday(self) -> Weekday {
+        WEEKDAYS[nonneg(self.0.rem_euclid(7)) as usize]
+    }
+}
+
+/// A day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    /// Sunday.
+    Sunday,
+    /// Monday.
+    Monday,
+    /// Tuesday.
+    Tuesday,
+    /// Wednesday.
+    Wednesday,
+    /// Thursday.
+    Thursday,
+    /// Friday.
+    Friday,
+    /// Saturday.
+    Saturday,
+}
+
+// Indexed by R.D. modulo 7; R.D. 0 is a Sunday
+pub(crate) const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Sunday,
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+];
+
+// =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=
+
+/// A moment in time in some time zone.
+///
+/// Functions that take a moment accept any `Time`, and functions that find a moment relative to
+/// another return it in the same time zone. This is implemented for [`Moment`] (in Universal Time),
+/// Chrono's `DateTime`, the `time` crate's `OffsetDateTime`, and Jiff's `Timestamp` and `Zoned`.
+pub trait Time: Clone {
+    /// Returns the moment.
+    fn to_moment(&self) -> Moment;
+
+    /// Returns `moment` in the time zone of `self`, or `None` if it cannot be represented.
+    fn with_moment(&self, moment: Moment) -> Option<Self>;
+
+    /// Returns the date of the moment in its time zone.
+    fn fixed_date(&self) -> FixedDate;
+}
+
+/// A [`Time`] that can be created from a moment alone, such as a moment in UTC.
+///
+/// Iterators and other functions that produce moments without an input moment to take a time zone
+/// from return a `Timestamp`. This is implemented for [`Moment`], Chrono's `DateTime<Utc>`, the
+/// `time` crate's `OffsetDateTime` (returned in UTC), and Jiff's `Timestamp`.
+pub trait Timestamp: Time {
+    /// Returns `moment`, or `None` if it cannot be represented.
+    fn from_moment(moment: Moment) -> Option<Self>;
+
+    /// Returns the earliest moment that can be represented.
+    fn min_value() -> Self;
+
+    /// Returns the latest moment that can be represented.
+    fn max_value() -> Self;
+}
+
+/// A calendar date in some time zone.
+///
+/// This is implemented for [`FixedDate`] (a day in Universal Time), the civil date types of each
+/// date-time library (a day in UTC), and Chrono's `DateTime`, the `time` crate's `OffsetDateTime`
+/// and Jiff's `Zoned` (the day each falls on in its own time zone). The deprecated Chrono `Date` is
+/// supported with the `compat-date` feature.
+pub trait Day: Clone {
+    /// The type of moments during the day.
+    type Time: Timestamp;
+
+    /// Returns the date, regardless of time zone.
+    fn fixed_date(&self) -> FixedDate;
+
+    /// Returns the moments at which the day begins and ends.
+    fn bounds(&self) -> (Moment, Moment);
+
+    /// Returns the day containing `moment` in the time zone of `self`, or `None` if it cannot be
+    /// represented.
+    fn day_containing(&self, moment: Moment) -> Option<Self>;
+}
+
+/// A [`Day`] that can be created from a date alone, such as a date in UTC.
+///
+/// Calendar conversions and iterators of days return a `CivilDate`. This is implemented for
+/// [`FixedDate`], Chrono's `NaiveDate`, the `time` crate's `Date` and Jiff's civil `Date`, and for
+/// `Date<Utc>` with the `compat-date` feature.
+pub trait CivilDate: Day {
+    /// Returns `date`, or `None` if it cannot be represented.
+    fn from_fixed(date: FixedDate) -> Option<Self>;
+
+    /// Returns the earliest date that can be represented.
+    fn min_value() -> Self;
+
+    /// Returns the latest date that can be represented.
+    fn max_value() -> Self;
+}
+
+/// A [`CivilDate`] that can be placed in a time zone of type `Z`.
+///
+/// This is implemented for Chrono's `NaiveDate` in any Chrono `TimeZone`, the `time` crate's
+/// `Date` at a `UtcOffset`, and Jiff's civil `Date` in a Jiff `TimeZone`.
+pub trait LocalDate<Z>: CivilDate {
+    /// The type of moments in the time zone, each of which stands for the day it falls on.
+    type Local: Day;
+
+    /// Returns the moments at which the date begins and ends in `zone`.
+    ///
+    /// These are not necessarily a day apart, such as on days when daylight saving time begins or
+    /// ends.
+    fn local_bounds(&self, zone: &Z) -> (Moment, Moment);
+
+    /// Returns `moment` in `zone`, or `None` if it cannot be represented.
+    fn local_time(zone: &Z, moment: Moment) -> Option<Self::Local>;
+}
+
+impl Time for Moment {
+    fn to_moment(&self) -> Moment {
+        *self
+    }
+
+    fn with_moment(&self, moment: Moment) -> Option<Moment> {
+        Some(moment)
+    }
+
+    fn fixed_date(&self) -> FixedDate {
+        Moment::fixed_date(*self)
+    }
+}
+
+impl Timestamp for Moment {
+    fn from_moment(moment: Moment) -> Option<Moment> {
+        Some(moment)
+    }
+
+    fn min_value() -> Moment {
+        Moment(MIN_MOMENT)
+    }
+
+    fn max_value() -> Moment {
+        Moment(MAX_MOMENT)
+    }
+}
+
+impl Day for FixedDate {
+    type Time = Moment;
+
+    fn fixed_date(&self) -> FixedDate {
+        *self
+    }
+
+    fn bounds(&self) -> (Moment, Moment) {
+        (self.moment(), Moment(f64::from(self.0) + 1.0))
+    }
+
+    fn day_containing(&self, moment: Moment) -> Option<FixedDate> {
+        Some(moment.fixed_date())
+    }
+}
+
+impl CivilDate for FixedDate {
+    fn from_fixed(date: FixedDate) -> Option<FixedDate> {
+        Some(date)
+    }
+
+    fn min_value() -> FixedDate {
+        Moment(MIN_MOMENT).fixed_date()
+    }
+
+    fn max_value() -> FixedDate {
+        Moment(MAX_MOMENT).fixed_date()
+    }
+}
+
+// A time of day in hours, minutes, seconds and nanoseconds, as the date-time libraries represent it
+pub(crate) type TimeOfDay = (u32, u32, u32, u32);
+
+// The moment of a date in the proleptic Gregorian calendar and a time of day
+#[cfg_attr(not(any(feature = "chrono", feature = "jiff", feature = "time")), allow(dead_code))]
+pub(crate) fn moment_from_parts((year, month, day): (i32, u32, u32), time: TimeOfDay) -> Moment {
+    let (hour, min, sec, nano) = time;
+    let rd_sec = f64::from(hour * 3600 + min * 60 + sec) + (f64::from(nano) / 1_000_000_000.0);
+    Moment(f64::from(fixed_from_gregorian(year, month, day)) + (rd_sec / 86400.0))
+}
+
+// The date in the proleptic Gregorian calendar and time of day of a moment
+#[cfg_attr(not(any(feature = "chrono", feature = "jiff", feature = "time")), allow(dead_code))]
+pub(crate) fn parts_from_moment(moment: Moment) -> ((i32, u32, u32), TimeOfDay) {
+    let fract = rem_euclid(moment.0, 1.0) * 86400.0;
+    let hour = u32(fract / 3600.0);
+    let min = u32(fract % 3600.0 / 60.0);
+    let sec = u32(fract % 60.0);
+    let nano = u32((fract % 1.0) * 1_000_000_000.0);
+    (gregorian_from_fixed(moment.0), (hour, min, sec, nano))
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+#[test]
+fn test_moment_conversions() {
+    let t = Moment::from_rd(730_120.5);
+    assert_eq!(t.jd(), 2_451_545.0);
+    assert_eq!(t.mjd(), 51_544.5);
+    assert_eq!(t.unix_timestamp(), 946_728_000.0);
+    assert_eq!(t.j2000(), 0.0);
+    assert_eq!(Moment::from_jd(t.jd()), t);
+    assert_eq!(Moment::from_mjd(t.mjd()), t);
+    assert_eq!(Moment::from_unix_timestamp(t.unix_timestamp()), t);
+    assert_eq!(Moment::from_j2000(t.j2000()), t);
+    assert_eq!(t.fixed_date(), FixedDate::from_gregorian(2000, 1, 1).unwrap());
+
+    assert_eq!(Moment::from_unix_timestamp(0.0).fixed_date().to_gregorian(), (1970, 1, 1));
+    assert_eq!(Moment::from_mjd(0.0).fixed_date().to_gregorian(), (1858, 11, 17));
+    assert_eq!(Moment::from_rd(-0.5).fixed_date().rd(), -1);
+    assert_eq!(FixedDate::from_rd(1).to_gregorian(), (1, 1, 1));
+
+    assert!(FixedDate::from_gregorian(2021, 2, 29).is_none());
+    assert!(FixedDate::from_gregorian(2020, 2, 29).is_some());
+    assert!(FixedDate::from_gregorian(2020, 13, 1).is_none());
+    assert!(FixedDate::from_gregorian(MAX_YEAR, 12, 31).is_some());
+    assert!(FixedDate::from_gregorian(MIN_YEAR, 1, 1).is_some());
+    assert!(FixedDate::from_gregorian(i32::MAX, 1, 1).is_none());
+}
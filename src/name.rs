@@ -0,0 +1,288 @@
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+
+use crate::calendar::{
+    lunar_phase_at_or_after, lunar_phase_at_or_before, solar_longitude_after, MEAN_TROPICAL_YEAR,
+};
+use crate::delta_t::{DeltaT, EspenakMeeus};
+use crate::location::Hemisphere;
+use crate::moment::Time;
+
+const FULL_MOON: f64 = 180.0;
+
+/// A traditional name for a full moon.
+///
+/// The months given are those of the northern hemisphere; see [`full_moon_name`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FullMoonName {
+    /// The January full moon in both traditions.
+    Wolf,
+    /// The February full moon in the almanac, or the November full moon of the Celtic names.
+    Snow,
+    /// The March full moon in the almanac.
+    Worm,
+    /// The April full moon in the almanac.
+    Pink,
+    /// The May full moon in the almanac.
+    Flower,
+    /// The June full moon in the almanac.
+    Strawberry,
+    /// The July full moon in the almanac.
+    Buck,
+    /// The August full moon in the almanac.
+    Sturgeon,
+    /// The September full moon, unless it is the Harvest Moon.
+    Corn,
+    /// The full moon nearest the autumn equinox.
+    Harvest,
+    /// The full moon after the Harvest Moon.
+    Hunters,
+    /// The November full moon, unless it is the Hunter's Moon.
+    Beaver,
+    /// The December full moon in the almanac.
+    Cold,
+    /// The February full moon of the Celtic names.
+    Storm,
+    /// The March full moon of the Celtic names.
+    Chaste,
+    /// The April full moon of the Celtic names.
+    Seed,
+    /// The May full moon of the Celtic names.
+    Hare,
+    /// The June full moon of the Celtic names.
+    Dyad,
+    /// The July full moon of the Celtic names.
+    Mead,
+    /// The August full moon of the Celtic names.
+    Wort,
+    /// The September full moon of the Celtic names.
+    Barley,
+    /// The October full moon of the Celtic names.
+    Blood,
+    /// The December full moon of the Celtic names.
+    Oak,
+}
+
+/// A set of names for the full moons of the year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FullMoonTradition {
+    /// The names published by the Old Farmer's Almanac, which are named for the calendar month
+    /// the full moon falls in.
+    ///
+    /// The full moon nearest the autumn equinox is the Harvest Moon, and the full moon after it is
+    /// the Hunter's Moon. These replace the name the full moon would otherwise have.
+    OldFarmersAlmanac,
+    /// The names popularly attributed to the Celts, which are named for the calendar month the
+    /// full moon falls in.
+    Celtic,
+}
+
+impl FullMoonName {
+    /// Returns the English name of the full moon.
+    ///
+    /// ```
+    /// # use esbat::FullMoonName;
+    /// assert_eq!(FullMoonName::Hunters.as_str(), "Hunter's Moon");
+    /// ```
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FullMoonName::Wolf => "Wolf Moon",
+            FullMoonName::Snow => "Snow Moon",
+            FullMoonName::Worm => "Worm Moon",
+            FullMoonName::Pink => "Pink Moon",
+            FullMoonName::Flower => "Flower Moon",
+            FullMoonName::Strawberry => "Strawberry Moon",
+            FullMoonName::Buck => "Buck Moon",
+            FullMoonName::Sturgeon => "Sturgeon Moon",
+            FullMoonName::Corn => "Corn Moon",
+            FullMoonName::Harvest => "Harvest Moon",
+            FullMoonName::Hunters => "Hunter's Moon",
+            FullMoonName::Beaver => "Beaver Moon",
+            FullMoonName::Cold => "Cold Moon",
+            FullMoonName::Storm => "Storm Moon",
+            FullMoonName::Chaste => "Chaste Moon",
+            FullMoonName::Seed => "Seed Moon",
+            FullMoonName::Hare => "Hare Moon",
+            FullMoonName::Dyad => "Dyad Moon",
+            FullMoonName::Mead => "Mead Moon",
+            FullMoonName::Wort => "Wort Moon",
+            FullMoonName::Barley => "Barley Moon",
+            FullMoonName::Blood => "Blood Moon",
+            FullMoonName::Oak => "Oak Moon",
+        }
+    }
+}
+
+const ALMANAC_NAMES: [FullMoonName; 12] = [
+    FullMoonName::Wolf,
+    FullMoonName::Snow,
+    FullMoonName::Worm,
+    FullMoonName::Pink,
+    FullMoonName::Flower,
+    FullMoonName::Strawberry,
+    FullMoonName::Buck,
+    FullMoonName::Sturgeon,
+    FullMoonName::Corn,
+    FullMoonName::Hunters,
+    FullMoonName::Beaver,
+    FullMoonName::Cold,
+];
+
+const CELTIC_NAMES: [FullMoonName; 12] = [
+    FullMoonName::Wolf,
+    FullMoonName::Storm,
+    FullMoonName::Chaste,
+    FullMoonName::Seed,
+    FullMoonName::Hare,
+    FullMoonName::Dyad,
+    FullMoonName::Mead,
+    FullMoonName::Wort,
+    FullMoonName::Barley,
+    FullMoonName::Blood,
+    FullMoonName::Snow,
+    FullMoonName::Oak,
+];
+
+impl FullMoonTradition {
+    // Takes a month (1 through 12) of the northern hemisphere calendar.
+    fn by_month(self, month: u32) -> FullMoonName {
+        let names = match self {
+            FullMoonTradition::OldFarmersAlmanac => &ALMANAC_NAMES,
+            FullMoonTradition::Celtic => &CELTIC_NAMES,
+        };
+        names[month as usize - 1]
+    }
+}
+
+// Returns the moment of the full moon nearest the equinox at solar longitude `lambda` that falls
+// within half a year of moment `t`.
+fn harvest_moon(lambda: f64, t: f64, dt: &dyn DeltaT) -> f64 {
+    let equinox = solar_longitude_after(lambda, t - MEAN_TROPICAL_YEAR / 2.0, dt);
+    let before = lunar_phase_at_or_before(FULL_MOON, equinox, dt);
+    let after = lunar_phase_at_or_after(FULL_MOON, equinox, dt);
+    if equinox - before < after - equinox {
+        before
+    } else {
+        after
+    }
+}
+
+/// Returns the traditional name of the full moon at moment `t`.
+///
+/// `t` should be the moment of a full moon, such as one returned by
+/// [`lunar_phase_iter`](crate::lunar_phase_iter). The calendar month is determined in the time
+/// zone of `t`.
+///
+/// In the southern hemisphere, the names are shifted by six months to follow the seasons, and the
+/// Harvest Moon is the full moon nearest the March equinox.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{TimeZone, Utc};
+/// use esbat::{FullMoonName, FullMoonTradition, Hemisphere};
+///
+/// let t = Utc.with_ymd_and_hms(2020, 10, 1, 21, 5, 0).unwrap();
+/// let name = esbat::full_moon_name(t, FullMoonTradition::OldFarmersAlmanac, Hemisphere::Northern);
+/// assert_eq!(name, FullMoonName::Harvest);
+/// let name = esbat::full_moon_name(t, FullMoonTradition::OldFarmersAlmanac, Hemisphere::Southern);
+/// assert_eq!(name, FullMoonName::Pink);
+/// let name = esbat::full_moon_name(t, FullMoonTradition::Celtic, Hemisphere::Northern);
+/// assert_eq!(name, FullMoonName::Blood);
+/// # }
+/// ```
+pub fn full_moon_name<T: Time>(
+    t: T,
+    tradition: FullMoonTradition,
+    hemisphere: Hemisphere,
+) -> FullMoonName {
+    full_moon_name_with(t, tradition, hemisphere, &EspenakMeeus)
+}
+
+pub(crate) fn full_moon_name_with<T: Time>(
+    t: T,
+    tradition: FullMoonTradition,
+    hemisphere: Hemisphere,
+    dt: &dyn DeltaT,
+) -> FullMoonName {
+    let (_, month, _) = t.fixed_date().to_gregorian();
+    let (month, equinox) = match hemisphere {
+        Hemisphere::Northern => (month, 180.0),
+        Hemisphere::Southern => ((month + 5) % 12 + 1, 0.0),
+    };
+    let t = t.to_moment().rd();
+
+    if tradition == FullMoonTradition::OldFarmersAlmanac {
+        let harvest = harvest_moon(equinox, t, dt);
+        if (t - harvest).abs() < 1.0 {
+            return FullMoonName::Harvest;
+        } else if (t - lunar_phase_at_or_after(FULL_MOON, harvest + 1.0, dt)).abs() < 1.0 {
+            return FullMoonName::Hunters;
+        }
+    }
+    tradition.by_month(month)
+}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_full_moon_name() {
+    use crate::phase::PrincipalPhase;
+    use chrono::{TimeZone, Utc};
+
+    let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+    let names = crate::lunar_phase_iter(start..end)
+        .filter(|(phase, _)| *phase == PrincipalPhase::FullMoon)
+        .map(|(_, t)| full_moon_name(t, FullMoonTradition::OldFarmersAlmanac, Hemisphere::Northern))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            FullMoonName::Wolf,
+            FullMoonName::Snow,
+            FullMoonName::Worm,
+            FullMoonName::Pink,
+            FullMoonName::Flower,
+            FullMoonName::Strawberry,
+            FullMoonName::Buck,
+            FullMoonName::Sturgeon,
+            FullMoonName::Corn,
+            FullMoonName::Harvest,
+            FullMoonName::Hunters,
+            FullMoonName::Beaver,
+            FullMoonName::Cold,
+        ]
+    );
+
+    // The 2019 Harvest Moon fell in September, so the October full moon was the Hunter's Moon
+    let t = Utc.with_ymd_and_hms(2019, 9, 14, 4, 33, 0).unwrap();
+    assert_eq!(
+        full_moon_name(t, FullMoonTradition::OldFarmersAlmanac, Hemisphere::Northern),
+        FullMoonName::Harvest
+    );
+    let t = Utc.with_ymd_and_hms(2019, 10, 13, 21, 8, 0).unwrap();
+    assert_eq!(
+        full_moon_name(t, FullMoonTradition::OldFarmersAlmanac, Hemisphere::Northern),
+        FullMoonName::Hunters
+    );
+    let t = Utc.with_ymd_and_hms(2019, 11, 12, 13, 34, 0).unwrap();
+    assert_eq!(
+        full_moon_name(t, FullMoonTradition::OldFarmersAlmanac, Hemisphere::Northern),
+        FullMoonName::Beaver
+    );
+
+    // In the southern hemisphere, the March full moon was nearest the March equinox
+    let t = Utc.with_ymd_and_hms(2020, 3, 9, 17, 48, 0).unwrap();
+    assert_eq!(
+        full_moon_name(t, FullMoonTradition::OldFarmersAlmanac, Hemisphere::Southern),
+        FullMoonName::Harvest
+    );
+    let t = Utc.with_ymd_and_hms(2020, 4, 8, 2, 35, 0).unwrap();
+    assert_eq!(
+        full_moon_name(t, FullMoonTradition::OldFarmersAlmanac, Hemisphere::Southern),
+        FullMoonName::Hunters
+    );
+}
@@ -0,0 +1,532 @@
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+
+#![allow(clippy::unreadable_literal)]
+
+use crate::calendar::{
+    lunar_longitude, lunar_phase, lunar_phase_at_or_after, lunar_phase_at_or_before, precession,
+    solar_longitude,
+};
+use crate::delta_t::{DeltaT, EspenakMeeus};
+use crate::iter::{moment_bounds, Angle, AngleIter};
+use crate::moment::{Moment, Time, Timestamp};
+use crate::util::{clamp_angle, i32, nonneg, round, u32};
+use core::iter::FusedIterator;
+use core::ops::RangeBounds;
+
+/// An ayanamsha, the offset between the tropical and sidereal zodiacs used to find the Moon's
+/// nakshatra and the yoga.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ayanamsha {
+    /// The Lahiri (Chitrapaksha) ayanamsha, the standard of the Indian national calendar.
+    Lahiri,
+    /// The ayanamsha of B. V. Raman.
+    Raman,
+    /// The ayanamsha of K. S. Krishnamurti.
+    Krishnamurti,
+    /// The Fagan-Bradley ayanamsha used in Western sidereal astrology.
+    FaganBradley,
+    /// An ayanamsha with the given value at J2000.0, in degrees.
+    Custom(f64),
+}
+
+impl Ayanamsha {
+    fn at_j2000(self) -> f64 {
+        match self {
+            Ayanamsha::Lahiri => 23.857092,
+            Ayanamsha::Raman => 22.410791,
+            Ayanamsha::Krishnamurti => 23.760240,
+            Ayanamsha::FaganBradley => 24.740300,
+            Ayanamsha::Custom(value) => value,
+        }
+    }
+
+    fn sidereal(self, longitude: f64, t: f64, dt: &dyn DeltaT) -> f64 {
+        clamp_angle(longitude - self.at_j2000() - precession(t, dt))
+    }
+}
+
+/// A paksha, the waxing or waning half of a lunar month.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Paksha {
+    /// The bright (waxing) fortnight, from new moon to full moon.
+    Shukla,
+    /// The dark (waning) fortnight, from full moon to new moon.
+    Krishna,
+}
+
+/// A tithi, a lunar day. Each of the 30 tithis in a lunar month lasts while the lunar phase
+/// increases by 12&deg;.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tithi {
+    /// The half of the lunar month.
+    pub paksha: Paksha,
+    /// The tithi within the paksha, from 1 to 15.
+    pub day: u32,
+}
+
+const TITHI_NAMES: [&str; 14] = [
+    "Pratipada",
+    "Dwitiya",
+    "Tritiya",
+    "Chaturthi",
+    "Panchami",
+    "Shashthi",
+    "Saptami",
+    "Ashtami",
+    "Navami",
+    "Dashami",
+    "Ekadashi",
+    "Dwadashi",
+    "Trayodashi",
+    "Chaturdashi",
+];
+
+impl Tithi {
+    fn from_number(number: u32) -> Tithi {
+        debug_assert!((1..=30).contains(&number));
+        if number <= 15 {
+            Tithi { paksha: Paksha::Shukla, day: number }
+        } else {
+            Tithi { paksha: Paksha::Krishna, day: number - 15 }
+        }
+    }
+
+    /// Returns the tithi's number within the lunar month, from 1 (Shukla Pratipada) to 30
+    /// (Amavasya).
+    ///
+    /// ```
+    /// use esbat::{Paksha, Tithi};
+    ///
+    /// assert_eq!(Tithi { paksha: Paksha::Krishna, day: 1 }.number(), 16);
+    /// ```
+    pub fn number(self) -> u32 {
+        match self.paksha {
+            Paksha::Shukla => self.day,
+            Paksha::Krishna => self.day + 15,
+        }
+    }
+
+    /// Returns the name of the tithi within its paksha, or an empty string if `day` is not from 1
+    /// to 15.
+    ///
+    /// ```
+    /// use esbat::{Paksha, Tithi};
+    ///
+    /// assert_eq!(Tithi { paksha: Paksha::Shukla, day: 11 }.as_str(), "Ekadashi");
+    /// assert_eq!(Tithi { paksha: Paksha::Shukla, day: 15 }.as_str(), "Purnima");
+    /// assert_eq!(Tithi { paksha: Paksha::Krishna, day: 15 }.as_str(), "Amavasya");
+    /// assert_eq!(Tithi { paksha: Paksha::Krishna, day: 0 }.as_str(), "");
+    /// ```
+    pub fn as_str(self) -> &'static str {
+        match (self.paksha, self.day) {
+            (Paksha::Shukla, 15) => "Purnima",
+            (Paksha::Krishna, 15) => "Amavasya",
+            (_, day) => TITHI_NAMES.get(day.wrapping_sub(1) as usize).copied().unwrap_or(""),
+        }
+    }
+}
+
+/// A nakshatra, one of the 27 lunar mansions of 13&deg;20&prime; of sidereal longitude.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Nakshatra {
+    /// From 0&deg; to 13&deg;20&prime; of sidereal longitude.
+    Ashwini,
+    /// From 13&deg;20&prime; to 26&deg;40&prime; of sidereal longitude.
+    Bharani,
+    /// From 26&deg;40&prime; to 40&deg; of sidereal longitude.
+    Krittika,
+    /// From 40&deg; to 53&deg;20&prime; of sidereal longitude.
+    Rohini,
+    /// From 53&deg;20&prime; to 66&deg;40&prime; of sidereal longitude.
+    Mrigashira,
+    /// From 66&deg;40&prime; to 80&deg; of sidereal longitude.
+    Ardra,
+    /// From 80&deg; to 93&deg;20&prime; of sidereal longitude.
+    Punarvasu,
+    /// From 93&deg;20&prime; to 106&deg;40&prime; of sidereal longitude.
+    Pushya,
+    /// From 106&deg;40&prime; to 120&deg; of sidereal longitude.
+    Ashlesha,
+    /// From 120&deg; to 133&deg;20&prime; of sidereal longitude.
+    Magha,
+    /// From 133&deg;20&prime; to 146&deg;40&prime; of sidereal longitude.
+    PurvaPhalguni,
+    /// From 146&deg;40&prime; to 160&deg; of sidereal longitude.
+    UttaraPhalguni,
+    /// From 160&deg; to 173&deg;20&prime; of sidereal longitude.
+    Hasta,
+    /// From 173&deg;20&prime; to 186&deg;40&prime; of sidereal longitude.
+    Chitra,
+    /// From 186&deg;40&prime; to 200&deg; of sidereal longitude.
+    Swati,
+    /// From 200&deg; to 213&deg;20&prime; of sidereal longitude.
+    Vishakha,
+    /// From 213&deg;20&prime; to 226&deg;40&prime; of sidereal longitude.
+    Anuradha,
+    /// From 226&deg;40&prime; to 240&deg; of sidereal longitude.
+    Jyeshtha,
+    /// From 240&deg; to 253&deg;20&prime; of sidereal longitude.
+    Mula,
+    /// From 253&deg;20&prime; to 266&deg;40&prime; of sidereal longitude.
+    PurvaAshadha,
+    /// From 266&deg;40&prime; to 280&deg; of sidereal longitude.
+    UttaraAshadha,
+    /// From 280&deg; to 293&deg;20&prime; of sidereal longitude.
+    Shravana,
+    /// From 293&deg;20&prime; to 306&deg;40&prime; of sidereal longitude.
+    Dhanishta,
+    /// From 306&deg;40&prime; to 320&deg; of sidereal longitude.
+    Shatabhisha,
+    /// From 320&deg; to 333&deg;20&prime; of sidereal longitude.
+    PurvaBhadrapada,
+    /// From 333&deg;20&prime; to 346&deg;40&prime; of sidereal longitude.
+    UttaraBhadrapada,
+    /// From 346&deg;40&prime; to 360&deg; of sidereal longitude.
+    Revati,
+}
+
+const NAKSHATRAS: [Nakshatra; 27] = [
+    Nakshatra::Ashwini,
+    Nakshatra::Bharani,
+    Nakshatra::Krittika,
+    Nakshatra::Rohini,
+    Nakshatra::Mrigashira,
+    Nakshatra::Ardra,
+    Nakshatra::Punarvasu,
+    Nakshatra::Pushya,
+    Nakshatra::Ashlesha,
+    Nakshatra::Magha,
+    Nakshatra::PurvaPhalguni,
+    Nakshatra::UttaraPhalguni,
+    Nakshatra::Hasta,
+    Nakshatra::Chitra,
+    Nakshatra::Swati,
+    Nakshatra::Vishakha,
+    Nakshatra::Anuradha,
+    Nakshatra::Jyeshtha,
+    Nakshatra::Mula,
+    Nakshatra::PurvaAshadha,
+    Nakshatra::UttaraAshadha,
+    Nakshatra::Shravana,
+    Nakshatra::Dhanishta,
+    Nakshatra::Shatabhisha,
+    Nakshatra::PurvaBhadrapada,
+    Nakshatra::UttaraBhadrapada,
+    Nakshatra::Revati,
+];
+
+impl Nakshatra {
+    /// Views the 27 nakshatras as an iterator, beginning with Ashwini.
+    ///
+    /// ```
+    /// # use esbat::Nakshatra;
+    /// assert_eq!(Nakshatra::iter().next(), Some(&Nakshatra::Ashwini));
+    /// assert_eq!(Nakshatra::iter().len(), 27);
+    /// ```
+    pub fn iter() -> core::slice::Iter<'static, Nakshatra> {
+        NAKSHATRAS.iter()
+    }
+}
+
+/// A yoga, one of 27 divisions of 13&deg;20&prime; of the sum of the sidereal longitudes of the
+/// Sun and Moon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Yoga {
+    /// From 0&deg; to 13&deg;20&prime; of the sum of the longitudes.
+    Vishkambha,
+    /// From 13&deg;20&prime; to 26&deg;40&prime; of the sum of the longitudes.
+    Priti,
+    /// From 26&deg;40&prime; to 40&deg; of the sum of the longitudes.
+    Ayushman,
+    /// From 40&deg; to 53&deg;20&prime; of the sum of the longitudes.
+    Saubhagya,
+    /// From 53&deg;20&prime; to 66&deg;40&prime; of the sum of the longitudes.
+    Shobhana,
+    /// From 66&deg;40&prime; to 80&deg; of the sum of the longitudes.
+    Atiganda,
+    /// From 80&deg; to 93&deg;20&prime; of the sum of the longitudes.
+    Sukarma,
+    /// From 93&deg;20&prime; to 106&deg;40&prime; of the sum of the longitudes.
+    Dhriti,
+    /// From 106&deg;40&prime; to 120&deg; of the sum of the longitudes.
+    Shula,
+    /// From 120&deg; to 133&deg;20&prime; of the sum of the longitudes.
+    Ganda,
+    /// From 133&deg;20&prime; to 146&deg;40&prime; of the sum of the longitudes.
+    Vriddhi,
+    /// From 146&deg;40&prime; to 160&deg; of the sum of the longitudes.
+    Dhruva,
+    /// From 160&deg; to 173&deg;20&prime; of the sum of the longitudes.
+    Vyaghata,
+    /// From 173&deg;20&prime; to 186&deg;40&prime; of the sum of the longitudes.
+    Harshana,
+    /// From 186&deg;40&prime; to 200&deg; of the sum of the longitudes.
+    Vajra,
+    /// From 200&deg; to 213&deg;20&prime; of the sum of the longitudes.
+    Siddhi,
+    /// From 213&deg;20&prime; to 226&deg;40&prime; of the sum of the longitudes.
+    Vyatipata,
+    /// From 226&deg;40&prime; to 240&deg; of the sum of the longitudes.
+    Variyana,
+    /// From 240&deg; to 253&deg;20&prime; of the sum of the longitudes.
+    Parigha,
+    /// From 253&deg;20&prime; to 266&deg;40&prime; of the sum of the longitudes.
+    Shiva,
+    /// From 266&deg;40&prime; to 280&deg; of the sum of the longitudes.
+    Siddha,
+    /// From 280&deg; to 293&deg;20&prime; of the sum of the longitudes.
+    Sadhya,
+    /// From 293&deg;20&prime; to 306&deg;40&prime; of the sum of the longitudes.
+    Shubha,
+    /// From 306&deg;40&prime; to 320&deg; of the sum of the longitudes.
+    Shukla,
+    /// From 320&deg; to 333&deg;20&prime; of the sum of the longitudes.
+    Brahma,
+    /// From 333&deg;20&prime; to 346&deg;40&prime; of the sum of the longitudes.
+    Indra,
+    /// From 346&deg;40&prime; to 360&deg; of the sum of the longitudes.
+    Vaidhriti,
+}
+
+const YOGAS: [Yoga; 27] = [
+    Yoga::Vishkambha,
+    Yoga::Priti,
+    Yoga::Ayushman,
+    Yoga::Saubhagya,
+    Yoga::Shobhana,
+    Yoga::Atiganda,
+    Yoga::Sukarma,
+    Yoga::Dhriti,
+    Yoga::Shula,
+    Yoga::Ganda,
+    Yoga::Vriddhi,
+    Yoga::Dhruva,
+    Yoga::Vyaghata,
+    Yoga::Harshana,
+    Yoga::Vajra,
+    Yoga::Siddhi,
+    Yoga::Vyatipata,
+    Yoga::Variyana,
+    Yoga::Parigha,
+    Yoga::Shiva,
+    Yoga::Siddha,
+    Yoga::Sadhya,
+    Yoga::Shubha,
+    Yoga::Shukla,
+    Yoga::Brahma,
+    Yoga::Indra,
+    Yoga::Vaidhriti,
+];
+
+impl Yoga {
+    /// Views the 27 yogas as an iterator, beginning with Vishkambha.
+    ///
+    /// ```
+    /// # use esbat::Yoga;
+    /// assert_eq!(Yoga::iter().next(), Some(&Yoga::Vishkambha));
+    /// assert_eq!(Yoga::iter().len(), 27);
+    /// ```
+    pub fn iter() -> core::slice::Iter<'static, Yoga> {
+        YOGAS.iter()
+    }
+}
+
+/// A karana, half of a tithi.
+///
+/// The first karana of the lunar month is Kimstughna, followed by eight repetitions of the seven
+/// movable karanas (Bava through Vishti), and then Shakuni, Chatushpada and Naga.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Karana {
+    /// The first movable karana.
+    Bava,
+    /// The second movable karana.
+    Balava,
+    /// The third movable karana.
+    Kaulava,
+    /// The fourth movable karana.
+    Taitila,
+    /// The fifth movable karana.
+    Gara,
+    /// The sixth movable karana.
+    Vanija,
+    /// The seventh movable karana, also called Bhadra.
+    Vishti,
+    /// The fixed karana of the second half of Krishna Chaturdashi.
+    Shakuni,
+    /// The fixed karana of the first half of Amavasya.
+    Chatushpada,
+    /// The fixed karana of the second half of Amavasya.
+    Naga,
+    /// The fixed karana of the first half of Shukla Pratipada.
+    Kimstughna,
+}
+
+const MOVABLE_KARANAS: [Karana; 7] = [
+    Karana::Bava,
+    Karana::Balava,
+    Karana::Kaulava,
+    Karana::Taitila,
+    Karana::Gara,
+    Karana::Vanija,
+    Karana::Vishti,
+];
+
+impl Karana {
+    // Takes the karana's index within the lunar month, from 0 to 59
+    fn from_index(index: u32) -> Karana {
+        match index {
+            0 => Karana::Kimstughna,
+            57 => Karana::Shakuni,
+            58 => Karana::Chatushpada,
+            59 => Karana::Naga,
+            _ => MOVABLE_KARANAS[((index - 1) % 7) as usize],
+        }
+    }
+}
+
+/// The elements of the Hindu almanac at a moment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Panchanga {
+    /// The lunar day.
+    pub tithi: Tithi,
+    /// The lunar mansion of the Moon.
+    pub nakshatra: Nakshatra,
+    /// The yoga of the Sun and Moon.
+    pub yoga: Yoga,
+    /// The half of the lunar day.
+    pub karana: Karana,
+}
+
+fn division(angle: f64, size: f64) -> u32 {
+    u32(angle / size)
+}
+
+/// Returns the tithi, nakshatra, yoga and karana at a moment.
+///
+/// The nakshatra and yoga are measured in sidereal longitude, using `ayanamsha`.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{TimeZone, Utc};
+/// use esbat::{Ayanamsha, Karana, Nakshatra, Paksha, Tithi};
+///
+/// // Diwali
+/// let t = Utc.with_ymd_and_hms(2020, 11, 14, 12, 0, 0).unwrap();
+/// let panchanga = esbat::panchanga(t, Ayanamsha::Lahiri);
+/// assert_eq!(panchanga.tithi, Tithi { paksha: Paksha::Krishna, day: 15 });
+/// assert_eq!(panchanga.karana, Karana::Chatushpada);
+/// # }
+/// ```
+pub fn panchanga<T: Time>(t: T, ayanamsha: Ayanamsha) -> Panchanga {
+    panchanga_with(t, ayanamsha, &EspenakMeeus)
+}
+
+pub(crate) fn panchanga_with<T: Time>(t: T, ayanamsha: Ayanamsha, dt: &dyn DeltaT) -> Panchanga {
+    let t = t.to_moment().rd();
+    let phase = lunar_phase(t, dt);
+    let moon = ayanamsha.sidereal(lunar_longitude(t, dt), t, dt);
+    let sun = ayanamsha.sidereal(solar_longitude(t, dt), t, dt);
+    let mansion = 360.0 / 27.0;
+    Panchanga {
+        tithi: Tithi::from_number(division(phase, 12.0).min(29) + 1),
+        nakshatra: NAKSHATRAS[division(moon, mansion).min(26) as usize],
+        yoga: YOGAS[division(clamp_angle(sun + moon), mansion).min(26) as usize],
+        karana: Karana::from_index(division(phase, 6.0).min(59)),
+    }
+}
+
+/// Returns an iterator of tithis and the moments they begin.
+///
+/// ```
+/// use esbat::{FixedDate, Paksha, Tithi};
+///
+/// let start = FixedDate::from_gregorian(2020, 10, 16).unwrap().moment();
+/// let end = FixedDate::from_gregorian(2020, 10, 19).unwrap().moment();
+/// let mut iter = esbat::tithi_iter(start..end);
+///
+/// let (tithi, t) = iter.next().unwrap();
+/// assert_eq!(tithi, Tithi { paksha: Paksha::Shukla, day: 1 });
+/// assert_eq!(t.fixed_date(), FixedDate::from_gregorian(2020, 10, 16).unwrap());
+/// assert_eq!(iter.next().unwrap().0, Tithi { paksha: Paksha::Shukla, day: 2 });
+/// assert_eq!(iter.next().unwrap().0, Tithi { paksha: Paksha::Shukla, day: 3 });
+/// assert!(iter.next().is_none());
+/// ```
+pub fn tithi_iter<T, B>(range: B) -> TithiIter<T>
+where
+    T: Timestamp,
+    B: RangeBounds<T>,
+{
+    let (start, end) = moment_bounds(&range);
+    TithiIter::new(start, end, EspenakMeeus)
+}
+
+/// Tithi iterator.
+///
+/// This struct is created by [`tithi_iter`] or
+/// [`Ephemeris::tithi_iter`](crate::Ephemeris::tithi_iter), which determines the ΔT model `M`.
+#[derive(Debug, Clone)]
+pub struct TithiIter<T = Moment, M = EspenakMeeus> {
+    inner: AngleIter<T, M>,
+}
+
+impl<T: Timestamp, M: DeltaT> TithiIter<T, M> {
+    pub(crate) fn new(start: (f64, bool), end: (f64, bool), model: M) -> TithiIter<T, M> {
+        TithiIter {
+            inner: AngleIter::new(
+                Angle {
+                    value: lunar_phase,
+                    at_or_after: lunar_phase_at_or_after,
+                    at_or_before: lunar_phase_at_or_before,
+                    step: 12.0,
+                    // Tithis can be shorter than a day
+                    skip: 1.0 / 24.0,
+                },
+                start,
+                end,
+                model,
+            ),
+        }
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> Iterator for TithiIter<T, M> {
+    type Item = (Tithi, T);
+
+    fn next(&mut self) -> Option<(Tithi, T)> {
+        let (angle, next) = self.inner.next()?;
+        let number = nonneg(i32(round(angle / 12.0))) % 30 + 1;
+        Some((Tithi::from_number(number), next))
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> FusedIterator for TithiIter<T, M> {}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_panchanga() {
+    use chrono::{TimeZone, Utc};
+
+    // Sharad Purnima, when the Moon moves from Ashwini to Bharani at 12:29 UTC
+    let before =
+        panchanga(Utc.with_ymd_and_hms(2020, 10, 31, 12, 15, 0).unwrap(), Ayanamsha::Lahiri);
+    let after =
+        panchanga(Utc.with_ymd_and_hms(2020, 10, 31, 12, 45, 0).unwrap(), Ayanamsha::Lahiri);
+    assert_eq!(before.tithi.as_str(), "Purnima");
+    assert_eq!(before.karana, Karana::Bava);
+    assert_eq!(before.nakshatra, Nakshatra::Ashwini);
+    assert_eq!(after.nakshatra, Nakshatra::Bharani);
+
+    let start = Utc.with_ymd_and_hms(2020, 11, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2020, 10, 1, 0, 0, 0).unwrap();
+    let numbers = tithi_iter(start..end).map(|(tithi, _)| tithi.number()).collect::<Vec<_>>();
+    assert_eq!(numbers.len(), 31);
+    assert_eq!(numbers[0], 16);
+    for pair in numbers.windows(2) {
+        assert_eq!(pair[1] % 30 + 1, pair[0]);
+    }
+}
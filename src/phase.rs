@@ -82,10 +82,7 @@ impl Phase {
     /// assert_eq!(Phase::NewMoon.is_principal(), true);
     /// ```
     pub fn is_principal(self) -> bool {
-        match self {
-            Phase::NewMoon | Phase::FirstQuarter | Phase::FullMoon | Phase::LastQuarter => true,
-            _ => false,
-        }
+        matches!(self, Phase::NewMoon | Phase::FirstQuarter | Phase::FullMoon | Phase::LastQuarter)
     }
 
     pub(crate) fn from_phase_range(start: f64, mut end: f64) -> Phase {
@@ -177,12 +174,16 @@ impl PrincipalPhase {
         .iter()
     }
 
-    pub(crate) fn as_angle(self) -> f64 {
-        match self {
-            PrincipalPhase::NewMoon => NEW_MOON,
-            PrincipalPhase::FirstQuarter => FIRST_QUARTER,
-            PrincipalPhase::FullMoon => FULL_MOON,
-            PrincipalPhase::LastQuarter => LAST_QUARTER,
+    pub(crate) fn from_angle(angle: f64) -> PrincipalPhase {
+        debug_assert!((clamp_angle(angle) - angle).abs() < f64::EPSILON);
+        if angle < FIRST_QUARTER {
+            PrincipalPhase::NewMoon
+        } else if angle < FULL_MOON {
+            PrincipalPhase::FirstQuarter
+        } else if angle < LAST_QUARTER {
+            PrincipalPhase::FullMoon
+        } else {
+            PrincipalPhase::LastQuarter
         }
     }
 }
@@ -0,0 +1,223 @@
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+
+use crate::calendar;
+use crate::delta_t::EspenakMeeus;
+use crate::location::Location;
+use crate::moment::{Day, Moment, Timestamp};
+
+// Finds an event with `f` between the moments `date` begins and ends
+pub(crate) fn on_day<D, F>(date: D, f: F) -> Option<D::Time>
+where
+    D: Day,
+    F: FnOnce(f64, f64) -> Option<f64>,
+{
+    let (start, end) = date.bounds();
+    D::Time::from_moment(Moment::from_rd(f(start.rd(), end.rd())?))
+}
+
+/// Calculates the moment of moonrise on a given date at a given location.
+///
+/// Moonrise is the moment the upper limb of the Moon appears over the horizon, accounting for
+/// parallax, atmospheric refraction, and the observer's elevation. The date `date` runs from
+/// midnight to midnight in its time zone. Returns `None` if the Moon does not rise on that date,
+/// which happens about once a month at most latitudes.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{NaiveDate, Timelike};
+/// use esbat::{moonrise, Location};
+///
+/// let greenwich = Location::new(51.4778, -0.0014, 46.0);
+/// let rise = moonrise(NaiveDate::from_ymd_opt(2020, 10, 31).unwrap(), greenwich).unwrap();
+/// assert_eq!((rise.hour(), rise.minute()), (16, 51));
+/// # }
+/// ```
+pub fn moonrise<D: Day>(date: D, location: Location) -> Option<D::Time> {
+    on_day(date, |start, end| calendar::moonrise(start, end, location, &EspenakMeeus))
+}
+
+/// Calculates the moment of moonset on a given date at a given location.
+///
+/// Moonset is the moment the upper limb of the Moon disappears below the horizon, accounting for
+/// parallax, atmospheric refraction, and the observer's elevation. The date `date` runs from
+/// midnight to midnight in its time zone. Returns `None` if the Moon does not set on that date.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{NaiveDate, Timelike};
+/// use esbat::{moonset, Location};
+///
+/// let greenwich = Location::new(51.4778, -0.0014, 46.0);
+/// let set = moonset(NaiveDate::from_ymd_opt(2020, 10, 31).unwrap(), greenwich).unwrap();
+/// assert_eq!((set.hour(), set.minute()), (6, 23));
+/// # }
+/// ```
+pub fn moonset<D: Day>(date: D, location: Location) -> Option<D::Time> {
+    on_day(date, |start, end| calendar::moonset(start, end, location, &EspenakMeeus))
+}
+
+/// The depression of the Sun below the horizon that marks the beginning of dawn or the end of
+/// dusk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Twilight {
+    /// The Sun is 6&deg; below the horizon.
+    Civil,
+    /// The Sun is 12&deg; below the horizon.
+    Nautical,
+    /// The Sun is 18&deg; below the horizon.
+    Astronomical,
+}
+
+impl Twilight {
+    /// Returns the depression of the Sun below the horizon, in degrees.
+    ///
+    /// ```
+    /// # use esbat::Twilight;
+    /// assert_eq!(Twilight::Nautical.as_angle(), 12.0);
+    /// ```
+    pub fn as_angle(self) -> f64 {
+        match self {
+            Twilight::Civil => 6.0,
+            Twilight::Nautical => 12.0,
+            Twilight::Astronomical => 18.0,
+        }
+    }
+}
+
+/// Calculates the moment of sunrise on a given date at a given location.
+///
+/// Sunrise is the moment the upper limb of the Sun appears over the horizon, accounting for
+/// atmospheric refraction and the observer's elevation. The date `date` runs from midnight to
+/// midnight in its time zone. Returns `None` if the Sun does not rise on that date, such as during
+/// polar night or midnight sun.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{NaiveDate, Timelike};
+/// use esbat::{sunrise, Location};
+///
+/// let greenwich = Location::new(51.4778, -0.0014, 46.0);
+/// let rise = sunrise(NaiveDate::from_ymd_opt(2020, 10, 31).unwrap(), greenwich).unwrap();
+/// assert_eq!((rise.hour(), rise.minute()), (6, 50));
+/// # }
+/// ```
+pub fn sunrise<D: Day>(date: D, location: Location) -> Option<D::Time> {
+    on_day(date, |start, end| calendar::sunrise(start, end, location, &EspenakMeeus))
+}
+
+/// Calculates the moment of sunset on a given date at a given location.
+///
+/// Sunset is the moment the upper limb of the Sun disappears below the horizon, accounting for
+/// atmospheric refraction and the observer's elevation. The date `date` runs from midnight to
+/// midnight in its time zone. Returns `None` if the Sun does not set on that date.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{NaiveDate, Timelike};
+/// use esbat::{sunset, Location};
+///
+/// let greenwich = Location::new(51.4778, -0.0014, 46.0);
+/// let set = sunset(NaiveDate::from_ymd_opt(2020, 10, 31).unwrap(), greenwich).unwrap();
+/// assert_eq!((set.hour(), set.minute()), (16, 35));
+/// # }
+/// ```
+pub fn sunset<D: Day>(date: D, location: Location) -> Option<D::Time> {
+    on_day(date, |start, end| calendar::sunset(start, end, location, &EspenakMeeus))
+}
+
+/// Calculates the moment of dawn on a given date at a given location.
+///
+/// Dawn is the moment the center of the Sun rises to the depression given by `twilight`. The date
+/// `date` runs from midnight to midnight in its time zone. Returns `None` if the Sun does not
+/// cross that depression in the morning on that date, such as during summer at high latitudes.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{NaiveDate, Timelike};
+/// use esbat::{dawn, Location, Twilight};
+///
+/// let greenwich = Location::new(51.4778, -0.0014, 46.0);
+/// let date = NaiveDate::from_ymd_opt(2020, 10, 31).unwrap();
+/// let civil = dawn(date, greenwich, Twilight::Civil).unwrap();
+/// assert_eq!((civil.hour(), civil.minute()), (6, 17));
+///
+/// let date = NaiveDate::from_ymd_opt(2020, 6, 21).unwrap();
+/// assert!(dawn(date, greenwich, Twilight::Astronomical).is_none());
+/// # }
+/// ```
+pub fn dawn<D: Day>(date: D, location: Location, twilight: Twilight) -> Option<D::Time> {
+    on_day(date, |start, end| {
+        calendar::dawn(start, end, location, twilight.as_angle(), &EspenakMeeus)
+    })
+}
+
+/// Calculates the moment of dusk on a given date at a given location.
+///
+/// Dusk is the moment the center of the Sun sets to the depression given by `twilight`. The date
+/// `date` runs from midnight to midnight in its time zone. Returns `None` if the Sun does not
+/// cross that depression in the evening on that date.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::{NaiveDate, Timelike};
+/// use esbat::{dusk, Location, Twilight};
+///
+/// let greenwich = Location::new(51.4778, -0.0014, 46.0);
+/// let date = NaiveDate::from_ymd_opt(2020, 10, 31).unwrap();
+/// let civil = dusk(date, greenwich, Twilight::Civil).unwrap();
+/// assert_eq!((civil.hour(), civil.minute()), (17, 9));
+/// # }
+/// ```
+pub fn dusk<D: Day>(date: D, location: Location, twilight: Twilight) -> Option<D::Time> {
+    on_day(date, |start, end| {
+        calendar::dusk(start, end, location, twilight.as_angle(), &EspenakMeeus)
+    })
+}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_no_moonrise() {
+    use chrono::{Duration, NaiveDate};
+
+    // Roughly once a lunation, the Moon rises just after midnight one day and just before
+    // midnight the next, skipping a day entirely.
+    let greenwich = Location::new(51.4778, -0.0014, 46.0);
+    let start = NaiveDate::from_ymd_opt(2020, 10, 1).unwrap();
+    let days = (0..31).map(|n| start + Duration::days(n));
+    assert_eq!(days.clone().filter(|d| moonrise(*d, greenwich).is_none()).count(), 1);
+    assert_eq!(days.filter(|d| moonset(*d, greenwich).is_none()).count(), 1);
+
+    // Near the poles, the Moon may stay up or down for days at a time.
+    let longyearbyen = Location::new(78.22, 15.65, 0.0);
+    for date in &[
+        NaiveDate::from_ymd_opt(2020, 10, 8).unwrap(),
+        NaiveDate::from_ymd_opt(2020, 10, 22).unwrap(),
+    ] {
+        assert!(moonrise(*date, longyearbyen).is_none());
+        assert!(moonset(*date, longyearbyen).is_none());
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_polar_sun() {
+    use chrono::NaiveDate;
+
+    let longyearbyen = Location::new(78.22, 15.65, 0.0);
+    for date in &[
+        NaiveDate::from_ymd_opt(2020, 6, 21).unwrap(),
+        NaiveDate::from_ymd_opt(2020, 12, 21).unwrap(),
+    ] {
+        assert!(sunrise(*date, longyearbyen).is_none());
+        assert!(sunset(*date, longyearbyen).is_none());
+    }
+    let winter = NaiveDate::from_ymd_opt(2020, 12, 21).unwrap();
+    assert!(dawn(winter, longyearbyen, Twilight::Civil).is_none());
+    assert!(dawn(winter, longyearbyen, Twilight::Astronomical).is_some());
+    assert!(dusk(winter, longyearbyen, Twilight::Astronomical).is_some());
+}
@@ -0,0 +1,469 @@
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+
+use crate::calendar::{self, fixed_from_gregorian, gregorian_year_from_fixed, solar_longitude};
+use crate::delta_t::{DeltaT, EspenakMeeus};
+use crate::iter::{moment_bounds, Angle, AngleIter, Iter};
+use crate::location::Hemisphere;
+use crate::moment::{Moment, Timestamp};
+use crate::phase::PrincipalPhase;
+use crate::util::{clamp_angle, round};
+use core::iter::{FusedIterator, Peekable};
+use core::ops::RangeBounds;
+
+/// The eight sabbats of the Wheel of the Year.
+///
+/// The sabbats are the solstices and equinoxes (the quarter days) and the days roughly halfway
+/// between them (the cross-quarter days). In the southern hemisphere, each sabbat is observed at
+/// the opposite point of the year from the northern hemisphere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sabbat {
+    /// Midwinter; the December solstice in the northern hemisphere.
+    Yule,
+    /// The cross-quarter day between Yule and Ostara.
+    Imbolc,
+    /// The spring equinox; the March equinox in the northern hemisphere.
+    Ostara,
+    /// The cross-quarter day between Ostara and Litha.
+    Beltane,
+    /// Midsummer; the June solstice in the northern hemisphere.
+    Litha,
+    /// The cross-quarter day between Litha and Mabon.
+    Lughnasadh,
+    /// The autumn equinox; the September equinox in the northern hemisphere.
+    Mabon,
+    /// The cross-quarter day between Mabon and Yule.
+    Samhain,
+}
+
+/// How the dates of the sabbats are determined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SabbatMode {
+    /// Each sabbat falls on the same calendar date every year, at midnight UTC.
+    ///
+    /// See [`Sabbat::fixed_date`] for the dates used.
+    Fixed,
+    /// Each sabbat falls at the moment the Sun reaches a particular solar longitude.
+    ///
+    /// See [`Sabbat::solar_longitude`] for the longitudes used.
+    Astronomical,
+}
+
+impl Sabbat {
+    /// Views the eight sabbats as an iterator, beginning with Yule.
+    ///
+    /// ```
+    /// # use esbat::Sabbat;
+    /// assert_eq!(Sabbat::iter().next(), Some(&Sabbat::Yule));
+    /// assert_eq!(Sabbat::iter().len(), 8);
+    /// ```
+    pub fn iter() -> core::slice::Iter<'static, Sabbat> {
+        [
+            Sabbat::Yule,
+            Sabbat::Imbolc,
+            Sabbat::Ostara,
+            Sabbat::Beltane,
+            Sabbat::Litha,
+            Sabbat::Lughnasadh,
+            Sabbat::Mabon,
+            Sabbat::Samhain,
+        ]
+        .iter()
+    }
+
+    // Returns the sabbat observed on the same day in the other hemisphere.
+    fn opposite(self) -> Sabbat {
+        match self {
+            Sabbat::Yule => Sabbat::Litha,
+            Sabbat::Imbolc => Sabbat::Lughnasadh,
+            Sabbat::Ostara => Sabbat::Mabon,
+            Sabbat::Beltane => Sabbat::Samhain,
+            Sabbat::Litha => Sabbat::Yule,
+            Sabbat::Lughnasadh => Sabbat::Imbolc,
+            Sabbat::Mabon => Sabbat::Ostara,
+            Sabbat::Samhain => Sabbat::Beltane,
+        }
+    }
+
+    fn northern(self, hemisphere: Hemisphere) -> Sabbat {
+        match hemisphere {
+            Hemisphere::Northern => self,
+            Hemisphere::Southern => self.opposite(),
+        }
+    }
+
+    /// Returns the solar longitude at which the sabbat falls in the given hemisphere, in degrees.
+    ///
+    /// The quarter days fall on the solstices and equinoxes, and the cross-quarter days fall at
+    /// solar longitudes 45&deg;, 135&deg;, 225&deg;, and 315&deg;.
+    ///
+    /// ```
+    /// # use esbat::{Hemisphere, Sabbat};
+    /// assert_eq!(Sabbat::Samhain.solar_longitude(Hemisphere::Northern), 225.0);
+    /// assert_eq!(Sabbat::Samhain.solar_longitude(Hemisphere::Southern), 45.0);
+    /// ```
+    pub fn solar_longitude(self, hemisphere: Hemisphere) -> f64 {
+        match self.northern(hemisphere) {
+            Sabbat::Ostara => 0.0,
+            Sabbat::Beltane => 45.0,
+            Sabbat::Litha => 90.0,
+            Sabbat::Lughnasadh => 135.0,
+            Sabbat::Mabon => 180.0,
+            Sabbat::Samhain => 225.0,
+            Sabbat::Yule => 270.0,
+            Sabbat::Imbolc => 315.0,
+        }
+    }
+
+    /// Returns the month and day on which the sabbat falls in the given hemisphere when using
+    /// [`SabbatMode::Fixed`].
+    ///
+    /// ```
+    /// # use esbat::{Hemisphere, Sabbat};
+    /// assert_eq!(Sabbat::Samhain.fixed_date(Hemisphere::Northern), (10, 31));
+    /// assert_eq!(Sabbat::Samhain.fixed_date(Hemisphere::Southern), (5, 1));
+    /// ```
+    pub fn fixed_date(self, hemisphere: Hemisphere) -> (u32, u32) {
+        match self.northern(hemisphere) {
+            Sabbat::Yule => (12, 21),
+            Sabbat::Imbolc => (2, 1),
+            Sabbat::Ostara => (3, 21),
+            Sabbat::Beltane => (5, 1),
+            Sabbat::Litha => (6, 21),
+            Sabbat::Lughnasadh => (8, 1),
+            Sabbat::Mabon => (9, 21),
+            Sabbat::Samhain => (10, 31),
+        }
+    }
+
+    // Takes an angle relative to the start of spring in the given hemisphere.
+    fn from_angle(angle: f64) -> Sabbat {
+        debug_assert!((clamp_angle(angle) - angle).abs() < f64::EPSILON);
+        let index = round(angle / 45.0);
+        if index < 1.0 {
+            Sabbat::Ostara
+        } else if index < 2.0 {
+            Sabbat::Beltane
+        } else if index < 3.0 {
+            Sabbat::Litha
+        } else if index < 4.0 {
+            Sabbat::Lughnasadh
+        } else if index < 5.0 {
+            Sabbat::Mabon
+        } else if index < 6.0 {
+            Sabbat::Samhain
+        } else if index < 7.0 {
+            Sabbat::Yule
+        } else {
+            Sabbat::Imbolc
+        }
+    }
+}
+
+// Solar longitude measured from the September equinox, which begins spring in the southern
+// hemisphere.
+fn southern_solar_longitude(t: f64, dt: &dyn DeltaT) -> f64 {
+    clamp_angle(solar_longitude(t, dt) + 180.0)
+}
+
+fn southern_solar_longitude_after(lambda: f64, t: f64, dt: &dyn DeltaT) -> f64 {
+    calendar::solar_longitude_after(clamp_angle(lambda + 180.0), t, dt)
+}
+
+fn southern_solar_longitude_before(lambda: f64, t: f64, dt: &dyn DeltaT) -> f64 {
+    calendar::solar_longitude_before(clamp_angle(lambda + 180.0), t, dt)
+}
+
+// Finds the nearest fixed-date sabbat after (or before, if not `positive`) `t`.
+fn fixed_sabbat(
+    t: f64,
+    excl: bool,
+    positive: bool,
+    hemisphere: Hemisphere,
+) -> Option<(Sabbat, i32)> {
+    let year = gregorian_year_from_fixed(t);
+    let candidates = (year - 1..=year + 1)
+        .flat_map(|year| {
+            Sabbat::iter().map(move |sabbat| {
+                let (month, day) = sabbat.fixed_date(hemisphere);
+                (*sabbat, fixed_from_gregorian(year, month, day))
+            })
+        })
+        .filter(|(_, c)| {
+            let c = f64::from(*c);
+            if positive {
+                c > t || (c >= t && !excl)
+            } else {
+                c < t || (c <= t && !excl)
+            }
+        });
+    if positive {
+        candidates.min_by_key(|(_, c)| *c)
+    } else {
+        candidates.max_by_key(|(_, c)| *c)
+    }
+}
+
+/// Returns an iterator of sabbats and their moments.
+///
+/// ```
+/// use esbat::{FixedDate, Hemisphere, Sabbat, SabbatMode};
+///
+/// let start = FixedDate::from_gregorian(2020, 10, 1).unwrap().moment();
+/// let end = FixedDate::from_gregorian(2021, 1, 1).unwrap().moment();
+///
+/// let mut iter = esbat::sabbat_iter(start..end, SabbatMode::Fixed, Hemisphere::Northern);
+/// let midnight = |m, d| FixedDate::from_gregorian(2020, m, d).unwrap().moment();
+/// assert_eq!(iter.next().unwrap(), (Sabbat::Samhain, midnight(10, 31)));
+/// assert_eq!(iter.next().unwrap(), (Sabbat::Yule, midnight(12, 21)));
+/// assert!(iter.next().is_none());
+///
+/// let mut iter = esbat::sabbat_iter(start..end, SabbatMode::Astronomical, Hemisphere::Southern);
+/// let (sabbat, t) = iter.next().unwrap();
+/// assert_eq!(sabbat, Sabbat::Beltane);
+/// assert_eq!(t.fixed_date(), FixedDate::from_gregorian(2020, 11, 6).unwrap());
+/// assert_eq!(iter.next().unwrap().0, Sabbat::Litha);
+/// assert!(iter.next().is_none());
+/// ```
+pub fn sabbat_iter<T, B>(range: B, mode: SabbatMode, hemisphere: Hemisphere) -> SabbatIter<T>
+where
+    T: Timestamp,
+    B: RangeBounds<T>,
+{
+    let (start, end) = moment_bounds(&range);
+    SabbatIter::new(start, end, mode, hemisphere, EspenakMeeus)
+}
+
+/// Sabbat iterator.
+///
+/// This struct is created by [`sabbat_iter`] or
+/// [`Ephemeris::sabbat_iter`](crate::Ephemeris::sabbat_iter), which determines the ΔT model `M`.
+#[derive(Debug, Clone)]
+pub struct SabbatIter<T = Moment, M = EspenakMeeus> {
+    inner: SabbatIterInner<T, M>,
+    hemisphere: Hemisphere,
+}
+
+#[derive(Debug, Clone)]
+enum SabbatIterInner<T, M> {
+    Fixed { cursor: Option<(f64, bool)>, end: (f64, bool), positive: bool },
+    Astronomical(AngleIter<T, M>),
+}
+
+impl<T: Timestamp, M: DeltaT> SabbatIter<T, M> {
+    pub(crate) fn new(
+        start: (f64, bool),
+        end: (f64, bool),
+        mode: SabbatMode,
+        hemisphere: Hemisphere,
+        model: M,
+    ) -> SabbatIter<T, M> {
+        let inner = match (mode, hemisphere) {
+            (SabbatMode::Fixed, _) => {
+                SabbatIterInner::Fixed { cursor: Some(start), end, positive: start.0 <= end.0 }
+            }
+            (SabbatMode::Astronomical, Hemisphere::Northern) => {
+                SabbatIterInner::Astronomical(AngleIter::new(
+                    Angle {
+                        value: solar_longitude,
+                        at_or_after: calendar::solar_longitude_after,
+                        at_or_before: calendar::solar_longitude_before,
+                        step: 45.0,
+                        skip: 1.0,
+                    },
+                    start,
+                    end,
+                    model,
+                ))
+            }
+            (SabbatMode::Astronomical, Hemisphere::Southern) => {
+                SabbatIterInner::Astronomical(AngleIter::new(
+                    Angle {
+                        value: southern_solar_longitude,
+                        at_or_after: southern_solar_longitude_after,
+                        at_or_before: southern_solar_longitude_before,
+                        step: 45.0,
+                        skip: 1.0,
+                    },
+                    start,
+                    end,
+                    model,
+                ))
+            }
+        };
+        SabbatIter { inner, hemisphere }
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> Iterator for SabbatIter<T, M> {
+    type Item = (Sabbat, T);
+
+    fn next(&mut self) -> Option<(Sabbat, T)> {
+        match &mut self.inner {
+            SabbatIterInner::Fixed { cursor, end: (end, end_excl), positive } => {
+                let (start, start_excl) = (*cursor)?;
+                if let Some((sabbat, next)) =
+                    fixed_sabbat(start, start_excl, *positive, self.hemisphere)
+                {
+                    let next = f64::from(next);
+                    let in_range = match (*positive, *end_excl) {
+                        (true, false) => next <= *end,
+                        (true, true) => next < *end,
+                        (false, false) => next >= *end,
+                        (false, true) => next > *end,
+                    };
+                    if let (true, Some(t)) = (in_range, T::from_moment(Moment::from_rd(next))) {
+                        *cursor = Some((next, true));
+                        return Some((sabbat, t));
+                    }
+                }
+                *cursor = None;
+                None
+            }
+            SabbatIterInner::Astronomical(inner) => {
+                let (angle, next) = inner.next()?;
+                Some((Sabbat::from_angle(angle), next))
+            }
+        }
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> FusedIterator for SabbatIter<T, M> {}
+
+/// An event on the Wheel of the Year: either a sabbat or a principal phase of the Moon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WheelEvent {
+    /// A sabbat.
+    Sabbat(Sabbat),
+    /// A principal phase of the Moon.
+    Phase(PrincipalPhase),
+}
+
+/// Returns an iterator of sabbats and principal phases, in order, and their moments.
+///
+/// This merges the results of [`sabbat_iter`] and [`lunar_phase_iter`](crate::lunar_phase_iter).
+/// If a sabbat and a principal phase occur at the same moment, the sabbat is returned first.
+///
+/// ```
+/// use esbat::{FixedDate, Hemisphere, PrincipalPhase, Sabbat, SabbatMode, WheelEvent};
+///
+/// let start = FixedDate::from_gregorian(2020, 10, 24).unwrap().moment();
+/// let end = FixedDate::from_gregorian(2020, 11, 9).unwrap().moment();
+/// let mut iter = esbat::wheel_iter(start..end, SabbatMode::Fixed, Hemisphere::Northern);
+///
+/// assert_eq!(iter.next().unwrap().0, WheelEvent::Sabbat(Sabbat::Samhain));
+/// assert_eq!(iter.next().unwrap().0, WheelEvent::Phase(PrincipalPhase::FullMoon));
+/// assert_eq!(iter.next().unwrap().0, WheelEvent::Phase(PrincipalPhase::LastQuarter));
+/// assert!(iter.next().is_none());
+/// ```
+pub fn wheel_iter<T, B>(range: B, mode: SabbatMode, hemisphere: Hemisphere) -> WheelIter<T>
+where
+    T: Timestamp,
+    B: RangeBounds<T>,
+{
+    let (start, end) = moment_bounds(&range);
+    WheelIter::new(start, end, mode, hemisphere, EspenakMeeus)
+}
+
+/// Sabbat and principal phase iterator.
+///
+/// This struct is created by [`wheel_iter`] or
+/// [`Ephemeris::wheel_iter`](crate::Ephemeris::wheel_iter), which determines the ΔT model `M`.
+#[derive(Debug, Clone)]
+pub struct WheelIter<T: Timestamp = Moment, M: DeltaT = EspenakMeeus> {
+    phases: Peekable<Iter<T, M>>,
+    sabbats: Peekable<SabbatIter<T, M>>,
+    positive: bool,
+}
+
+impl<T: Timestamp, M: DeltaT + Clone> WheelIter<T, M> {
+    pub(crate) fn new(
+        start: (f64, bool),
+        end: (f64, bool),
+        mode: SabbatMode,
+        hemisphere: Hemisphere,
+        model: M,
+    ) -> WheelIter<T, M> {
+        WheelIter {
+            phases: Iter::new(start, end, model.clone()).peekable(),
+            sabbats: SabbatIter::new(start, end, mode, hemisphere, model).peekable(),
+            positive: start.0 <= end.0,
+        }
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> Iterator for WheelIter<T, M> {
+    type Item = (WheelEvent, T);
+
+    fn next(&mut self) -> Option<(WheelEvent, T)> {
+        let sabbat_first = match (self.phases.peek(), self.sabbats.peek()) {
+            (Some((_, phase)), Some((_, sabbat))) => {
+                let (phase, sabbat) = (phase.to_moment(), sabbat.to_moment());
+                if self.positive {
+                    sabbat <= phase
+                } else {
+                    sabbat >= phase
+                }
+            }
+            (None, Some(_)) => true,
+            (_, None) => false,
+        };
+        if sabbat_first {
+            self.sabbats.next().map(|(sabbat, t)| (WheelEvent::Sabbat(sabbat), t))
+        } else {
+            self.phases.next().map(|(phase, t)| (WheelEvent::Phase(phase), t))
+        }
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> FusedIterator for WheelIter<T, M> {}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_sabbat_iter() {
+    use crate::util::assert_events;
+    use chrono::{TimeZone, Utc};
+    use core::ops::Bound::{Excluded, Unbounded};
+
+    let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+
+    for hemisphere in &[Hemisphere::Northern, Hemisphere::Southern] {
+        let fixed = sabbat_iter(start..end, SabbatMode::Fixed, *hemisphere).collect::<Vec<_>>();
+        let astronomical =
+            sabbat_iter(start..end, SabbatMode::Astronomical, *hemisphere).collect::<Vec<_>>();
+        assert_eq!(fixed.len(), 8);
+        assert_eq!(astronomical.len(), 8);
+        for ((a, a_t), (b, b_t)) in fixed.iter().zip(astronomical.iter()) {
+            assert_eq!(a, b);
+            assert!((*a_t - *b_t).num_days().abs() <= 7);
+        }
+
+        let rev = sabbat_iter(end..start, SabbatMode::Astronomical, *hemisphere)
+            .map(|(sabbat, _)| sabbat)
+            .collect::<Vec<_>>();
+        assert_eq!(rev, astronomical.iter().rev().map(|(sabbat, _)| *sabbat).collect::<Vec<_>>());
+    }
+
+    // Cross-quarter days from the Hong Kong Observatory's tables of solar terms
+    let expected = [
+        (Sabbat::Imbolc, Utc.with_ymd_and_hms(2020, 2, 4, 9, 3, 0).unwrap()),
+        (Sabbat::Beltane, Utc.with_ymd_and_hms(2020, 5, 5, 0, 51, 0).unwrap()),
+        (Sabbat::Lughnasadh, Utc.with_ymd_and_hms(2020, 8, 7, 1, 6, 0).unwrap()),
+        (Sabbat::Samhain, Utc.with_ymd_and_hms(2020, 11, 6, 23, 14, 0).unwrap()),
+    ];
+    let actual = sabbat_iter(start..end, SabbatMode::Astronomical, Hemisphere::Northern)
+        .filter(|(sabbat, _)| expected.iter().any(|(s, _)| s == sabbat));
+    assert_events(actual, &expected, 2);
+
+    let samhain = Utc.with_ymd_and_hms(2020, 10, 31, 0, 0, 0).unwrap();
+    let mut iter = sabbat_iter(samhain.., SabbatMode::Fixed, Hemisphere::Northern);
+    assert_eq!(iter.next().unwrap().0, Sabbat::Samhain);
+    let mut iter =
+        sabbat_iter((Excluded(samhain), Unbounded), SabbatMode::Fixed, Hemisphere::Northern);
+    assert_eq!(iter.next().unwrap().0, Sabbat::Yule);
+}
@@ -0,0 +1,190 @@
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+
+use crate::calendar::{self, solar_longitude};
+use crate::delta_t::{DeltaT, EspenakMeeus};
+use crate::iter::{moment_bounds, Angle, AngleIter};
+use crate::moment::{Moment, Time, Timestamp};
+use crate::util::clamp_angle;
+use core::iter::FusedIterator;
+use core::ops::RangeBounds;
+
+const MARCH_EQUINOX: f64 = 0.0;
+const JUNE_SOLSTICE: f64 = 90.0;
+const SEPTEMBER_EQUINOX: f64 = 180.0;
+const DECEMBER_SOLSTICE: f64 = 270.0;
+
+/// The equinoxes and solstices that begin the four astronomical seasons.
+///
+/// The seasons are named by month rather than by season, since the March equinox begins spring in
+/// the northern hemisphere and autumn in the southern hemisphere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Season {
+    /// The Sun crosses the celestial equator moving north, at solar longitude 0&deg;.
+    MarchEquinox,
+    /// The Sun reaches its northernmost declination, at solar longitude 90&deg;.
+    JuneSolstice,
+    /// The Sun crosses the celestial equator moving south, at solar longitude 180&deg;.
+    SeptemberEquinox,
+    /// The Sun reaches its southernmost declination, at solar longitude 270&deg;.
+    DecemberSolstice,
+}
+
+impl Season {
+    /// Views the four equinoxes and solstices as an iterator.
+    ///
+    /// ```
+    /// # use esbat::Season;
+    /// assert_eq!(Season::iter().next(), Some(&Season::MarchEquinox));
+    /// assert_eq!(Season::iter().len(), 4);
+    /// ```
+    pub fn iter() -> core::slice::Iter<'static, Season> {
+        [
+            Season::MarchEquinox,
+            Season::JuneSolstice,
+            Season::SeptemberEquinox,
+            Season::DecemberSolstice,
+        ]
+        .iter()
+    }
+
+    /// Returns the solar longitude at which the season begins, in degrees.
+    ///
+    /// ```
+    /// # use esbat::Season;
+    /// assert_eq!(Season::SeptemberEquinox.as_angle(), 180.0);
+    /// ```
+    pub fn as_angle(self) -> f64 {
+        match self {
+            Season::MarchEquinox => MARCH_EQUINOX,
+            Season::JuneSolstice => JUNE_SOLSTICE,
+            Season::SeptemberEquinox => SEPTEMBER_EQUINOX,
+            Season::DecemberSolstice => DECEMBER_SOLSTICE,
+        }
+    }
+
+    pub(crate) fn from_angle(angle: f64) -> Season {
+        debug_assert!((clamp_angle(angle) - angle).abs() < f64::EPSILON);
+        if angle < JUNE_SOLSTICE {
+            Season::MarchEquinox
+        } else if angle < SEPTEMBER_EQUINOX {
+            Season::JuneSolstice
+        } else if angle < DECEMBER_SOLSTICE {
+            Season::SeptemberEquinox
+        } else {
+            Season::DecemberSolstice
+        }
+    }
+}
+
+/// Finds the first moment at or after `t` when the solar longitude is `angle` degrees.
+///
+/// The moment is returned in the time zone of `t`. Returns `None` if the moment cannot be
+/// represented by the type of `t`.
+///
+/// ```
+/// use esbat::{solar_longitude_after, FixedDate};
+///
+/// // The Sun enters Scorpio
+/// let t = FixedDate::from_gregorian(2020, 1, 1).unwrap().moment();
+/// let t = solar_longitude_after(210.0, t).unwrap();
+/// assert_eq!(t.fixed_date(), FixedDate::from_gregorian(2020, 10, 22).unwrap());
+/// ```
+pub fn solar_longitude_after<T: Time>(angle: f64, t: T) -> Option<T> {
+    let next =
+        calendar::solar_longitude_after(clamp_angle(angle), t.to_moment().rd(), &EspenakMeeus);
+    t.with_moment(Moment::from_rd(next))
+}
+
+/// Returns an iterator of equinoxes and solstices and their moments.
+///
+/// ```
+/// use esbat::{FixedDate, Season};
+///
+/// let start = FixedDate::from_gregorian(2020, 1, 1).unwrap().moment();
+/// let end = FixedDate::from_gregorian(2021, 1, 1).unwrap().moment();
+/// let mut iter = esbat::season_iter(start..end);
+///
+/// let (season, t) = iter.next().unwrap();
+/// assert_eq!(season, Season::MarchEquinox);
+/// assert_eq!(t.fixed_date(), FixedDate::from_gregorian(2020, 3, 20).unwrap());
+/// assert_eq!(iter.next().unwrap().0, Season::JuneSolstice);
+/// assert_eq!(iter.next().unwrap().0, Season::SeptemberEquinox);
+/// assert_eq!(iter.next().unwrap().0, Season::DecemberSolstice);
+/// assert!(iter.next().is_none());
+/// ```
+pub fn season_iter<T, B>(range: B) -> SeasonIter<T>
+where
+    T: Timestamp,
+    B: RangeBounds<T>,
+{
+    let (start, end) = moment_bounds(&range);
+    SeasonIter::new(start, end, EspenakMeeus)
+}
+
+/// Equinox and solstice iterator.
+///
+/// This struct is created by [`season_iter`] or
+/// [`Ephemeris::season_iter`](crate::Ephemeris::season_iter), which determines the ΔT model `M`.
+#[derive(Debug, Clone)]
+pub struct SeasonIter<T = Moment, M = EspenakMeeus> {
+    inner: AngleIter<T, M>,
+}
+
+impl<T: Timestamp, M: DeltaT> SeasonIter<T, M> {
+    pub(crate) fn new(start: (f64, bool), end: (f64, bool), model: M) -> SeasonIter<T, M> {
+        SeasonIter {
+            inner: AngleIter::new(
+                Angle {
+                    value: solar_longitude,
+                    at_or_after: calendar::solar_longitude_after,
+                    at_or_before: calendar::solar_longitude_before,
+                    step: 90.0,
+                    skip: 1.0,
+                },
+                start,
+                end,
+                model,
+            ),
+        }
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> Iterator for SeasonIter<T, M> {
+    type Item = (Season, T);
+
+    fn next(&mut self) -> Option<(Season, T)> {
+        let (angle, next) = self.inner.next()?;
+        Some((Season::from_angle(angle), next))
+    }
+}
+
+impl<T: Timestamp, M: DeltaT> FusedIterator for SeasonIter<T, M> {}
+
+#[cfg(test)]
+#[test]
+fn test_season_iter() {
+    use crate::moment::moment_from_parts;
+    use crate::util::assert_events;
+    use core::ops::Bound::{Excluded, Unbounded};
+
+    // Equinox and solstice times from the US Naval Observatory
+    let at = |y, m, d, h, min| moment_from_parts((y, m, d), (h, min, 0, 0));
+    let start = at(2021, 1, 1, 0, 0);
+    let end = at(2020, 1, 1, 0, 0);
+    let expected = [
+        (Season::DecemberSolstice, at(2020, 12, 21, 10, 2)),
+        (Season::SeptemberEquinox, at(2020, 9, 22, 13, 31)),
+        (Season::JuneSolstice, at(2020, 6, 20, 21, 44)),
+        (Season::MarchEquinox, at(2020, 3, 20, 3, 50)),
+    ];
+    assert_events(season_iter(start..end), &expected, 2);
+
+    let start = season_iter(end..).next().unwrap().1;
+    assert_eq!(season_iter(start..).next().unwrap().0, Season::MarchEquinox);
+    assert_eq!(season_iter((Excluded(start), Unbounded)).next().unwrap().0, Season::JuneSolstice);
+}
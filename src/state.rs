@@ -0,0 +1,153 @@
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+
+use crate::calendar::{
+    lunar_illumination, lunar_phase, new_moon_index_at_or_before, MEAN_SYNODIC_MONTH,
+};
+use crate::delta_t::{DeltaT, EspenakMeeus};
+use crate::moment::{Moment, Time};
+use crate::phase::{Phase, PrincipalPhase};
+use crate::util::{clamp_angle, floor, i32, inv_angle};
+
+/// A snapshot of the state of the Moon at a given moment.
+///
+/// This struct is created by [`lunar_state`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LunarState<T = Moment> {
+    /// The time since the previous new moon, in days.
+    pub age: f64,
+    /// The elongation of the Moon from the Sun along the ecliptic, in degrees. This is the same
+    /// value returned by [`lunar_phase`](crate::lunar_phase).
+    pub elongation: f64,
+    /// The illuminated fraction of the Moon's disc, from 0.0 to 1.0.
+    pub illumination: f64,
+    /// The phase of the Moon. This is a principal phase if one occurs within 12 hours of the
+    /// moment, or the intermediate phase after the previous principal phase otherwise.
+    pub phase: Phase,
+    /// The most recent principal phase at or before the moment, and its moment.
+    pub previous: (PrincipalPhase, T),
+    /// The next principal phase after the moment, and its moment.
+    pub next: (PrincipalPhase, T),
+    /// The fraction of the current lunation (from the previous new moon to the next) that has
+    /// elapsed, from 0.0 to 1.0.
+    pub lunation_fraction: f64,
+}
+
+/// Calculates a snapshot of the state of the Moon for a given moment.
+///
+/// This is more efficient than calculating each value separately, as the intermediate results are
+/// shared.
+///
+/// The moments of the surrounding principal phases are returned in the time zone of `time`.
+///
+/// # Panics
+///
+/// Panics if the surrounding principal phases are outside of the range of times the type of
+/// `time` can represent.
+///
+/// ```
+/// use esbat::{FixedDate, Phase, PrincipalPhase};
+///
+/// let state = esbat::lunar_state(FixedDate::from_gregorian(2020, 10, 27).unwrap().moment());
+/// assert_eq!(state.phase, Phase::WaxingGibbous);
+/// assert_eq!(state.previous.0, PrincipalPhase::FirstQuarter);
+/// assert_eq!(state.next.0, PrincipalPhase::FullMoon);
+/// assert_eq!(state.next.1.fixed_date(), FixedDate::from_gregorian(2020, 10, 31).unwrap());
+/// assert!((state.age - 10.2).abs() < 0.1);
+/// ```
+pub fn lunar_state<T: Time>(time: T) -> LunarState<T> {
+    lunar_state_with(time, &EspenakMeeus)
+}
+
+pub(crate) fn lunar_state_with<T: Time>(time: T, dt: &dyn DeltaT) -> LunarState<T> {
+    let t = time.to_moment().rd();
+    let (_, new_moon, next_new_moon, phi) = new_moon_index_at_or_before(t, dt);
+
+    // `lunar_phase` and `nth_new_moon` can disagree by moments about when a new moon occurs
+    let quarter = if phi >= 180.0 && t - new_moon < 1.0 {
+        0
+    } else if phi < 180.0 && next_new_moon - t < 1.0 {
+        3
+    } else {
+        i32(floor(phi / 90.0))
+    };
+    let previous_angle = f64::from(quarter) * 90.0;
+    let next_angle = clamp_angle(previous_angle + 90.0);
+    let rate = MEAN_SYNODIC_MONTH / 360.0;
+    let previous = if quarter == 0 {
+        new_moon
+    } else {
+        let tau = t - rate * clamp_angle(phi - previous_angle);
+        phase_within(previous_angle, tau, new_moon, t, dt)
+    };
+    let next = if quarter == 3 {
+        next_new_moon
+    } else {
+        let tau = t + rate * clamp_angle(next_angle - phi);
+        phase_within(next_angle, tau, t, next_new_moon, dt)
+    };
+
+    let previous_phase = PrincipalPhase::from_angle(previous_angle);
+    let next_phase = PrincipalPhase::from_angle(next_angle);
+    let phase = if t - previous < 0.5 {
+        previous_phase.into()
+    } else if next - t <= 0.5 {
+        next_phase.into()
+    } else {
+        match previous_phase {
+            PrincipalPhase::NewMoon => Phase::WaxingCrescent,
+            PrincipalPhase::FirstQuarter => Phase::WaxingGibbous,
+            PrincipalPhase::FullMoon => Phase::WaningGibbous,
+            PrincipalPhase::LastQuarter => Phase::WaningCrescent,
+        }
+    };
+
+    LunarState {
+        age: t - new_moon,
+        elongation: phi,
+        illumination: lunar_illumination(t, dt),
+        phase,
+        previous: (previous_phase, time.with_moment(Moment::from_rd(previous)).unwrap()),
+        next: (next_phase, time.with_moment(Moment::from_rd(next)).unwrap()),
+        lunation_fraction: (t - new_moon) / (next_new_moon - new_moon),
+    }
+}
+
+// The moment the lunar phase is `angle`, searching within two days of the estimate `tau` and within
+// the part of the lunation from `start` to `end`
+fn phase_within(angle: f64, tau: f64, start: f64, end: f64, dt: &dyn DeltaT) -> f64 {
+    inv_angle(|x| lunar_phase(x, dt), angle, start.max(tau - 2.0), end.min(tau + 2.0))
+}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_lunar_state() {
+    use chrono::{TimeZone, Utc};
+
+    let start = Utc.with_ymd_and_hms(2020, 10, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2020, 11, 1, 0, 0, 0).unwrap();
+    let phases = crate::lunar_phase_iter(start..end).collect::<Vec<_>>();
+    for pair in phases.windows(2) {
+        let (previous, next) = (pair[0], pair[1]);
+        let state = lunar_state(previous.1 + (next.1 - previous.1) / 2);
+        assert_eq!(state.previous.0, previous.0);
+        assert_eq!(state.next.0, next.0);
+        assert!((state.previous.1 - previous.1).num_seconds().abs() <= 1);
+        assert!((state.next.1 - next.1).num_seconds().abs() <= 1);
+        assert!(state.age > 0.0 && state.age < 29.6);
+        assert!(state.lunation_fraction > 0.0 && state.lunation_fraction < 1.0);
+    }
+
+    let state = lunar_state(phases[0].1);
+    assert_eq!(state.phase, Phase::FullMoon);
+    assert!((state.lunation_fraction - 0.5).abs() < 0.05);
+    assert!(state.illumination > 0.99);
+
+    for (phase, t) in phases {
+        assert_eq!(lunar_state(t).phase, Phase::from(phase));
+    }
+}
@@ -0,0 +1,230 @@
+// Copyright (c) 2018 Edward M. Reingold and Nachum Dershowitz
+// Copyright (c) 2020 iliana destroyer of worlds <iliana@buttslol.net>
+// SPDX-License-Identifier: CC-BY-NC-4.0
+//
+// This work is licensed under the Creative Commons Attribution-NonCommercial 4.0 International
+// License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
+// or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
+//
+// Functions from chapter 21, "The Tibetan Calendar", of:
+//
+//     E. M. Reingold and N. Dershowitz, Calendrical Calculations: The Ultimate Edition.
+//         Cambridge University Press, 2018. doi:10.1017/9781107415058
+//
+// See `calendar.rs` for the representation of dates and moments. The Phugpa calendar's mean motions
+// are rational numbers; they are kept in integer arithmetic where possible to avoid rounding error.
+
+#![allow(clippy::unreadable_literal)]
+
+use crate::moment::{CivilDate, Day, FixedDate};
+use crate::util::{amod, ceil, floor, i32, nonneg, u32};
+
+// fixed_from_gregorian(-127, 12, 7)
+const TIBETAN_EPOCH: i32 = -46410;
+
+const TIBETAN_SUN_EQUATION: [f64; 4] = [0.0, 6.0, 10.0, 11.0];
+const TIBETAN_MOON_EQUATION: [f64; 8] = [0.0, 5.0, 10.0, 15.0, 19.0, 22.0, 24.0, 25.0];
+
+// Linear interpolation of a table of equations in sixtieths, for `alpha` in [0, table.len() - 1]
+fn interpolate(table: &[f64], alpha: f64) -> f64 {
+    let i = u32(floor(alpha)) as usize;
+    let frac = alpha - floor(alpha);
+    let next = table[(i + 1).min(table.len() - 1)];
+    (table[i] * (1.0 - frac) + next * frac) / 60.0
+}
+
+fn tibetan_sun_equation(alpha: f64) -> f64 {
+    if alpha > 6.0 {
+        -tibetan_sun_equation(alpha - 6.0)
+    } else if alpha > 3.0 {
+        tibetan_sun_equation(6.0 - alpha)
+    } else {
+        interpolate(&TIBETAN_SUN_EQUATION, alpha)
+    }
+}
+
+fn tibetan_moon_equation(alpha: f64) -> f64 {
+    if alpha > 14.0 {
+        -tibetan_moon_equation(alpha - 14.0)
+    } else if alpha > 7.0 {
+        tibetan_moon_equation(14.0 - alpha)
+    } else {
+        interpolate(&TIBETAN_MOON_EQUATION, alpha)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn fixed_from_tibetan(year: i32, month: u32, leap_month: bool, day: u32, leap_day: bool) -> i32 {
+    let months = (804 * i64::from(year - 1) + 67 * i64::from(month) - 65 * i64::from(leap_month)
+        + 64)
+        .div_euclid(65);
+    let days = 30 * months + i64::from(day);
+    // mean = days * 11135/11312 - 30 + (leap_day ? 0 : -1) + 1071/1616
+    let mean = days * 11135 + 1071 * 7;
+    let mean_days = mean.div_euclid(11312) - 31 + i64::from(leap_day);
+    let mean_frac = mean.rem_euclid(11312) as f64 / 11312.0;
+    let solar_anomaly = (days * 13 + 2117).rem_euclid(4824) as f64 / 4824.0;
+    let lunar_anomaly = (days * 3781 + 2837 * 7).rem_euclid(105840) as f64 / 105840.0;
+    let sun = -tibetan_sun_equation(12.0 * solar_anomaly);
+    let moon = tibetan_moon_equation(28.0 * lunar_anomaly);
+    (i64::from(TIBETAN_EPOCH) + mean_days) as i32 + i32(floor(mean_frac + sun + moon))
+}
+
+fn tibetan_from_fixed(date: i32) -> (i32, u32, bool, u32, bool) {
+    let month_start = |year, month| fixed_from_tibetan(year, month, false, 1, false);
+
+    let cap_y = 365.0 + 4975.0 / 18382.0;
+    let mut year = i32(ceil(f64::from(date - TIBETAN_EPOCH) / cap_y));
+    while date < month_start(year, 1) {
+        year -= 1;
+    }
+    while date >= month_start(year + 1, 1) {
+        year += 1;
+    }
+    let mut month = 1;
+    while month < 13 && date >= month_start(year, month + 1) {
+        month += 1;
+    }
+    let mut day = (date - month_start(year, month) - 2).max(1);
+    while date >= fixed_from_tibetan(year, month, false, nonneg(day + 1), false) {
+        day += 1;
+    }
+
+    // Days past the 30th belong to a leap month that precedes the next month
+    let leap_month = day > 30;
+    if leap_month {
+        if month == 12 {
+            year += 1;
+        }
+        month = month % 12 + 1;
+    }
+    let day = nonneg(amod(day, 30));
+    let leap_day = date > fixed_from_tibetan(year, month, leap_month, day, false);
+    (year, month, leap_month, day, leap_day)
+}
+
+fn is_tibetan_leap_month(year: i32, month: u32) -> bool {
+    tibetan_from_fixed(fixed_from_tibetan(year, month, true, 2, false)).1 == month
+}
+
+// =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=   =^..^=
+
+/// A date in the Tibetan (Phugpa) calendar.
+///
+/// The Tibetan calendar is an arithmetic lunisolar calendar. A leap month is inserted before the
+/// month of the same number about every 32 months, and because each day is named for the lunar day
+/// (<i>tithi</i>) in progress at sunrise, day numbers are occasionally skipped or repeated.
+///
+/// Years are counted from 128 BCE, so that the Tibetan year beginning in 2020 is 2147.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TibetanDate {
+    /// The year.
+    pub year: i32,
+    /// The month, from 1 to 12.
+    pub month: u32,
+    /// True if the month is the leap month, which precedes the regular month of the same number.
+    pub leap_month: bool,
+    /// The day of the month, from 1 to 30.
+    pub day: u32,
+    /// True if the day is the second of two days with the same number.
+    pub leap_day: bool,
+}
+
+impl TibetanDate {
+    /// Converts a date in the proleptic Gregorian calendar to the Tibetan calendar.
+    ///
+    /// ```
+    /// use esbat::{FixedDate, TibetanDate};
+    ///
+    /// let date = TibetanDate::from_gregorian(FixedDate::from_gregorian(2020, 2, 24).unwrap());
+    /// assert_eq!(
+    ///     date,
+    ///     TibetanDate { year: 2147, month: 1, leap_month: false, day: 1, leap_day: false }
+    /// );
+    /// ```
+    pub fn from_gregorian<D: Day>(date: D) -> TibetanDate {
+        let (year, month, leap_month, day, leap_day) = tibetan_from_fixed(date.fixed_date().rd());
+        TibetanDate { year, month, leap_month, day, leap_day }
+    }
+
+    /// Converts the date in the Tibetan calendar to the proleptic Gregorian calendar.
+    ///
+    /// Returns `None` if the date cannot be represented by `D`.
+    pub fn to_gregorian<D: CivilDate>(self) -> Option<D> {
+        D::from_fixed(FixedDate::from_rd(fixed_from_tibetan(
+            self.year,
+            self.month,
+            self.leap_month,
+            self.day,
+            self.leap_day,
+        )))
+    }
+
+    /// Returns true if `month` in `year` is preceded by a leap month.
+    ///
+    /// ```
+    /// # use esbat::TibetanDate;
+    /// assert!(TibetanDate::is_leap_month(2146, 1));
+    /// assert!(!TibetanDate::is_leap_month(2147, 1));
+    /// ```
+    pub fn is_leap_month(year: i32, month: u32) -> bool {
+        is_tibetan_leap_month(year, month)
+    }
+}
+
+/// Returns the date of Losar, the Tibetan New Year, in a year of the Gregorian calendar.
+///
+/// Returns `None` if the date cannot be represented by `D`.
+///
+/// ```
+/// use esbat::FixedDate;
+///
+/// assert_eq!(esbat::losar(2021), Some(FixedDate::from_gregorian(2021, 2, 12).unwrap()));
+/// ```
+pub fn losar<D: CivilDate>(year: i32) -> Option<D> {
+    let year = year - crate::calendar::gregorian_year_from_fixed(f64::from(TIBETAN_EPOCH));
+    D::from_fixed(FixedDate::from_rd(fixed_from_tibetan(
+        year,
+        1,
+        is_tibetan_leap_month(year, 1),
+        1,
+        false,
+    )))
+}
+
+#[cfg(all(test, feature = "chrono"))]
+#[test]
+fn test_tibetan() {
+    use crate::conv::fixed_from_naive;
+    use chrono::NaiveDate;
+
+    assert_eq!(TIBETAN_EPOCH, crate::calendar::fixed_from_gregorian(-127, 12, 7));
+
+    let losars = [
+        (2018, (2, 16)),
+        (2019, (2, 5)),
+        (2020, (2, 24)),
+        (2021, (2, 12)),
+        (2022, (3, 3)),
+        (2023, (2, 21)),
+        (2024, (2, 10)),
+    ];
+    for (year, (month, day)) in &losars {
+        assert_eq!(losar(*year), Some(NaiveDate::from_ymd_opt(*year, *month, *day).unwrap()));
+    }
+
+    // Every date converts back to itself, including repeated days
+    let start = fixed_from_naive(NaiveDate::from_ymd_opt(2015, 1, 1).unwrap());
+    let mut leap_days = 0;
+    let mut leap_months = Vec::new();
+    for date in start..start + 3000 {
+        let (year, month, leap_month, day, leap_day) = tibetan_from_fixed(date);
+        assert_eq!(fixed_from_tibetan(year, month, leap_month, day, leap_day), date);
+        if leap_month && day == 1 {
+            leap_months.push((year, month));
+        }
+        leap_days += i32::from(leap_day);
+    }
+    assert!(leap_days > 0);
+    assert_eq!(leap_months, [(2143, 4), (2146, 1), (2148, 9)]);
+}
@@ -5,11 +5,93 @@
 // License. To view a copy of this license, visit https://creativecommons.org/licenses/by-nc/4.0/
 // or send a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
 
+// Floating-point functions that `core` doesn't provide, from the standard library or, in `no_std`
+// builds, from libm.
+//
+// The precision of the standard library's transcendental functions (and `powi`) is unspecified,
+// and they call the platform's math library, so their results can differ between targets. The
+// `deterministic` feature uses libm, a pure-Rust port of musl's math library, even when the
+// standard library is available; the other functions here are exact in both.
+#[cfg(not(all(feature = "libm", any(not(feature = "std"), feature = "deterministic"))))]
+mod math {
+    pub(crate) fn floor(x: f64) -> f64 {
+        x.floor()
+    }
+
+    pub(crate) fn ceil(x: f64) -> f64 {
+        x.ceil()
+    }
+
+    pub(crate) fn round(x: f64) -> f64 {
+        x.round()
+    }
+
+    pub(crate) fn trunc(x: f64) -> f64 {
+        x.trunc()
+    }
+
+    pub(crate) fn sqrt(x: f64) -> f64 {
+        x.sqrt()
+    }
+
+    pub(crate) fn powi(x: f64, n: i32) -> f64 {
+        x.powi(n)
+    }
+
+    pub(crate) fn rem_euclid(x: f64, y: f64) -> f64 {
+        x.rem_euclid(y)
+    }
+
+    pub(crate) fn sin(x: f64) -> f64 {
+        x.sin()
+    }
+
+    pub(crate) fn cos(x: f64) -> f64 {
+        x.cos()
+    }
+
+    pub(crate) fn tan(x: f64) -> f64 {
+        x.tan()
+    }
+
+    pub(crate) fn asin(x: f64) -> f64 {
+        x.asin()
+    }
+
+    pub(crate) fn acos(x: f64) -> f64 {
+        x.acos()
+    }
+
+    pub(crate) fn atan2(y: f64, x: f64) -> f64 {
+        y.atan2(x)
+    }
+}
+
+#[cfg(all(feature = "libm", any(not(feature = "std"), feature = "deterministic")))]
+mod math {
+    pub(crate) use libm::{acos, asin, atan2, ceil, cos, floor, round, sin, sqrt, tan, trunc};
+
+    pub(crate) fn powi(x: f64, n: i32) -> f64 {
+        libm::pow(x, f64::from(n))
+    }
+
+    pub(crate) fn rem_euclid(x: f64, y: f64) -> f64 {
+        let r = x % y;
+        if r < 0.0 {
+            r + y.abs()
+        } else {
+            r
+        }
+    }
+}
+
+pub(crate) use self::math::{ceil, floor, powi, rem_euclid, round, sqrt, trunc};
+
 macro_rules! f64_to {
     ($ty:ident) => {
-        #[allow(clippy::cast_possible_truncation)]
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
         pub(crate) fn $ty(x: f64) -> $ty {
-            let x = x.trunc();
+            let x = trunc(x);
             debug_assert!(f64::from($ty::MIN) <= x && x <= f64::from($ty::MAX));
             x as $ty
         }
@@ -24,16 +106,37 @@ pub(crate) fn nonneg(x: i32) -> u32 {
     x as u32
 }
 
+// Like `rem_euclid`, but returns `y` instead of 0
+pub(crate) fn amod(x: i32, y: i32) -> i32 {
+    (x - 1).rem_euclid(y) + 1
+}
+
 pub(crate) fn sin(x: f64) -> f64 {
-    x.to_radians().sin()
+    math::sin(x.to_radians())
 }
 
 pub(crate) fn cos(x: f64) -> f64 {
-    x.to_radians().cos()
+    math::cos(x.to_radians())
+}
+
+pub(crate) fn tan(x: f64) -> f64 {
+    math::tan(x.to_radians())
+}
+
+pub(crate) fn arcsin(x: f64) -> f64 {
+    math::asin(x).to_degrees()
+}
+
+pub(crate) fn arccos(x: f64) -> f64 {
+    math::acos(x).to_degrees()
+}
+
+pub(crate) fn arctan(y: f64, x: f64) -> f64 {
+    clamp_angle(math::atan2(y, x).to_degrees())
 }
 
 pub(crate) fn clamp_angle(x: f64) -> f64 {
-    x.rem_euclid(360.0)
+    rem_euclid(x, 360.0)
 }
 
 pub(crate) fn polynomial(base: f64, coefficients: &[f64]) -> f64 {
@@ -56,9 +159,9 @@ where
         if (start - end).abs() < f64::EPSILON {
             return start;
         }
-        let x = (start + end) / 2.0;
+        let x = start + (end - start) / 2.0;
         let diff = clamp_angle(f(x) - y);
-        if diff < 1e-5 || diff > (360.0 - 1e-5) {
+        if !(1e-5..=(360.0 - 1e-5)).contains(&diff) {
             break x;
         } else if diff < 180.0 {
             end = x;
@@ -67,3 +170,61 @@ where
         }
     }
 }
+
+// Finds a moment in [start, end] where `f` changes sign, as measured by `f(start)`. `f` must have
+// opposite signs at `start` and `end`.
+pub(crate) fn find_zero<F>(f: F, mut start: f64, mut end: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let positive = f(start) > 0.0;
+    while end - start > 1e-6 {
+        let x = start + (end - start) / 2.0;
+        if (f(x) > 0.0) == positive {
+            start = x;
+        } else {
+            end = x;
+        }
+    }
+    start + (end - start) / 2.0
+}
+
+// Finds the first moment in [start, end) where `f` becomes positive (if `rising`) or non-positive
+// (if not `rising`), sampling every `step` days.
+pub(crate) fn find_crossing<F>(f: F, start: f64, end: f64, step: f64, rising: bool) -> Option<f64>
+where
+    F: Fn(f64) -> f64,
+{
+    let mut a = start;
+    let mut fa = f(a);
+    while a < end {
+        let b = (a + step).min(end);
+        let fb = f(b);
+        if (fa <= 0.0 && fb > 0.0 && rising) || (fa > 0.0 && fb <= 0.0 && !rising) {
+            let x = find_zero(&f, a, b);
+            return if x < end { Some(x) } else { None };
+        }
+        a = b;
+        fa = fb;
+    }
+    None
+}
+
+// Asserts that `actual` yields the events in `expected`, each within `minutes` of its expected
+// moment
+#[cfg(test)]
+pub(crate) fn assert_events<E, T, I>(actual: I, expected: &[(E, T)], minutes: u32)
+where
+    E: PartialEq + core::fmt::Debug,
+    T: crate::moment::Time,
+    I: IntoIterator<Item = (E, T)>,
+{
+    let actual = actual.into_iter().collect::<Vec<_>>();
+    assert_eq!(actual.len(), expected.len());
+    for ((event, t), (expected_event, expected_t)) in actual.iter().zip(expected) {
+        let (t, expected_t) = (t.to_moment(), expected_t.to_moment());
+        assert_eq!(event, expected_event);
+        let error = (t.rd() - expected_t.rd()).abs() * 1440.0;
+        assert!(error <= f64::from(minutes), "{:?} at {:?}", event, t);
+    }
+}